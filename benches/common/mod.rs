@@ -0,0 +1,60 @@
+//! Deterministic point distributions shared by the benchmarks
+
+use std::f64::consts::PI;
+
+/// Names of the distributions accepted by [`points`]
+pub const DISTRIBUTIONS: [&str; 4] = ["uniform", "gaussian", "grid", "circle"];
+
+/// Generates `n` points of a distribution as `[x0, y0, x1, y1, ...]`
+///
+/// `grid` rounds down to the nearest square number of points, and `circle`
+/// puts every point on the same circle, so that all of them are cocircular
+/// and legalization does the most work.
+pub fn points(distribution: &str, n: usize) -> Vec<f64> {
+    let mut random = Random(0x9e3779b97f4a7c15);
+    let mut coords = Vec::with_capacity(2 * n);
+    match distribution {
+        "uniform" => {
+            for _ in 0..n {
+                coords.extend([random.next() * 1e3, random.next() * 1e3]);
+            }
+        }
+        "gaussian" => {
+            // Box-Muller transform
+            for _ in 0..n {
+                let r = (-2.0 * (1.0 - random.next()).ln()).sqrt() * 1e2;
+                let angle = 2.0 * PI * random.next();
+                coords.extend([r * angle.cos(), r * angle.sin()]);
+            }
+        }
+        "grid" => {
+            let side = (n as f64).sqrt() as usize;
+            for i in 0..side {
+                for j in 0..side {
+                    coords.extend([i as f64, j as f64]);
+                }
+            }
+        }
+        "circle" => {
+            for i in 0..n {
+                let angle = 2.0 * PI * i as f64 / n as f64;
+                coords.extend([1e3 * angle.cos(), 1e3 * angle.sin()]);
+            }
+        }
+        _ => panic!("unknown distribution {distribution}"),
+    }
+    coords
+}
+
+// Xorshift generator, so that every run triangulates the same points
+struct Random(u64);
+
+impl Random {
+    // Uniform in [0, 1)
+    fn next(&mut self) -> f64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        (self.0 >> 11) as f64 / (1u64 << 53) as f64
+    }
+}
@@ -0,0 +1,29 @@
+//! Benchmarks of the copies made by the JavaScript getters
+//!
+//! The getters build typed arrays on the JavaScript side, so these only run
+//! on wasm, with `wasm-bindgen-test-runner` as the cargo runner:
+//! `cargo bench --target wasm32-unknown-unknown --features wasm --bench getters`.
+
+#![cfg(target_arch = "wasm32")]
+
+mod common;
+
+use delaunator_rs::Delaunator;
+use wasm_bindgen_test::{wasm_bindgen_bench, Criterion};
+
+#[wasm_bindgen_bench]
+fn getters(c: &mut Criterion) {
+    for n in [1_000, 100_000] {
+        let delaunator = Delaunator::new(common::points("uniform", n)).unwrap();
+        c.bench_function(&format!("triangles/{n}"), |b| {
+            b.iter(|| delaunator.get_triangles())
+        });
+        c.bench_function(&format!("halfedges/{n}"), |b| {
+            b.iter(|| delaunator.get_halfedges())
+        });
+        c.bench_function(&format!("hull/{n}"), |b| b.iter(|| delaunator.get_hull()));
+        c.bench_function(&format!("coords/{n}"), |b| {
+            b.iter(|| delaunator.get_coords())
+        });
+    }
+}
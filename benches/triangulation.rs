@@ -0,0 +1,116 @@
+//! Triangulation benchmarks over standard point distributions
+//!
+//! Runs `new`, which allocates and triangulates, `update`, which
+//! triangulates again into the existing arrays, and `new` with each of the
+//! algorithms sorting the points by distance and each insertion order, from
+//! 1k points up to
+//! `DELAUNATOR_BENCH_MAX_POINTS` (1M by default, at most 10M), since the
+//! largest sizes take seconds per iteration. Select benchmarks with a
+//! filter, e.g. `cargo bench --bench triangulation -- new/uniform`.
+
+mod common;
+
+use std::env;
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use delaunator_rs::{Delaunator, Options, Order, Sort};
+
+const SIZES: [usize; 5] = [1_000, 10_000, 100_000, 1_000_000, 10_000_000];
+
+fn sizes() -> impl Iterator<Item = usize> {
+    let max = env::var("DELAUNATOR_BENCH_MAX_POINTS")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(1_000_000);
+    SIZES.into_iter().filter(move |&n| n <= max)
+}
+
+fn new(c: &mut Criterion) {
+    for distribution in common::DISTRIBUTIONS {
+        let mut group = c.benchmark_group(format!("new/{distribution}"));
+        for n in sizes() {
+            if n >= 1_000_000 {
+                group.sample_size(10);
+            }
+            let coords = common::points(distribution, n);
+            group.throughput(Throughput::Elements(n as u64));
+            group.bench_with_input(BenchmarkId::from_parameter(n), &coords, |b, coords| {
+                b.iter(|| Delaunator::new(coords.clone()).unwrap())
+            });
+        }
+        group.finish();
+    }
+}
+
+fn update(c: &mut Criterion) {
+    for distribution in common::DISTRIBUTIONS {
+        let mut group = c.benchmark_group(format!("update/{distribution}"));
+        for n in sizes() {
+            if n >= 1_000_000 {
+                group.sample_size(10);
+            }
+            let mut delaunator = Delaunator::new(common::points(distribution, n)).unwrap();
+            group.throughput(Throughput::Elements(n as u64));
+            group.bench_function(BenchmarkId::from_parameter(n), |b| {
+                b.iter(|| delaunator.update().unwrap())
+            });
+        }
+        group.finish();
+    }
+}
+
+fn sort(c: &mut Criterion) {
+    for distribution in common::DISTRIBUTIONS {
+        let mut group = c.benchmark_group(format!("sort/{distribution}"));
+        for n in sizes() {
+            if n >= 1_000_000 {
+                group.sample_size(10);
+            }
+            let coords = common::points(distribution, n);
+            group.throughput(Throughput::Elements(n as u64));
+            for sort in [Sort::Radix, Sort::Comparison, Sort::Quicksort] {
+                let id = BenchmarkId::new(format!("{sort:?}"), n);
+                group.bench_with_input(id, &coords, |b, coords| {
+                    b.iter(|| {
+                        Delaunator::with_options(
+                            coords.clone(),
+                            Options {
+                                sort,
+                                ..Options::default()
+                            },
+                        )
+                        .unwrap()
+                    })
+                });
+            }
+        }
+        group.finish();
+    }
+}
+
+fn order(c: &mut Criterion) {
+    for distribution in common::DISTRIBUTIONS {
+        let mut group = c.benchmark_group(format!("order/{distribution}"));
+        for n in sizes() {
+            if n >= 1_000_000 {
+                group.sample_size(10);
+            }
+            let coords = common::points(distribution, n);
+            group.throughput(Throughput::Elements(n as u64));
+            for order in [Order::Sweep, Order::Hilbert, Order::Brio] {
+                let id = BenchmarkId::new(format!("{order:?}"), n);
+                let options = Options {
+                    order,
+                    ..Options::default()
+                };
+                group.bench_with_input(id, &coords, |b, coords| {
+                    b.iter(|| Delaunator::with_options(coords.clone(), options).unwrap())
+                });
+            }
+        }
+        group.finish();
+    }
+}
+
+criterion_group!(benches, new, update, sort, order);
+criterion_main!(benches);
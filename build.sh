@@ -19,7 +19,7 @@ rm -rf pkg/ www/pkg/
 
 # Build the WASM package with optimizations
 echo "⚡ Building WebAssembly package with optimizations..."
-wasm-pack build --target web --release --out-dir pkg
+wasm-pack build wasm --target web --release --out-dir ../pkg --out-name delaunator_rs
 
 # Create www/pkg directory
 echo "📁 Creating www/pkg directory..."
@@ -0,0 +1,13 @@
+// Decodes fuzzer bytes into coordinates, either as raw doubles to reach
+// extreme and non-finite values, or as small integers to reach duplicate,
+// collinear and cocircular points
+pub fn coords(data: &[u8]) -> Vec<f64> {
+    match data.split_first() {
+        Some((mode, rest)) if mode % 2 == 0 => rest
+            .chunks_exact(8)
+            .map(|b| f64::from_le_bytes(b.try_into().unwrap()))
+            .collect(),
+        Some((_, rest)) => rest.iter().map(|&b| (b as i8) as f64).collect(),
+        None => Vec::new(),
+    }
+}
@@ -0,0 +1,17 @@
+#![no_main]
+
+use delaunator_rs::binary::View;
+use delaunator_rs::Delaunator;
+use libfuzzer_sys::fuzz_target;
+
+// Arbitrary bytes are rejected with an error, never a panic, and whatever
+// is accepted is a valid triangulation
+fuzz_target!(|data: &[u8]| {
+    if let Ok(d) = Delaunator::read_from(data) {
+        let report = d.validate();
+        assert!(report.is_valid(), "{report}");
+    }
+    if let Ok(view) = View::new(data) {
+        let _ = view.to_delaunator();
+    }
+});
@@ -0,0 +1,14 @@
+#![no_main]
+
+use delaunator_rs::Delaunator;
+use libfuzzer_sys::fuzz_target;
+
+mod common;
+
+fuzz_target!(|data: &[u8]| {
+    let coords = common::coords(data);
+    if let Ok(d) = Delaunator::new(coords) {
+        let report = d.validate();
+        assert!(report.is_structurally_valid(), "{report}");
+    }
+});
@@ -0,0 +1,21 @@
+#![no_main]
+
+use delaunator_rs::Delaunator;
+use libfuzzer_sys::fuzz_target;
+
+mod common;
+
+// Triangulating again into the same arrays gives the same result
+fuzz_target!(|data: &[u8]| {
+    let coords = common::coords(data);
+    if let Ok(mut d) = Delaunator::new(coords) {
+        let (triangles, halfedges, hull) =
+            (d.triangles.clone(), d.halfedges.clone(), d.hull.clone());
+        d.update().unwrap();
+        let report = d.validate();
+        assert!(report.is_structurally_valid(), "{report}");
+        assert_eq!(d.triangles, triangles);
+        assert_eq!(d.halfedges, halfedges);
+        assert_eq!(d.hull, hull);
+    }
+});
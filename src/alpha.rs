@@ -0,0 +1,201 @@
+//! Alpha shapes and concave hulls
+//!
+//! Both are subsets of the triangulation whose boundary hugs the points more
+//! tightly than the convex hull. An alpha shape keeps the triangles whose
+//! circumcircle is small enough and may have several components and holes.
+//! A concave hull erodes the triangulation from the outside and always stays
+//! a single simple polygon.
+
+use alloc::{collections::BinaryHeap, vec, vec::Vec};
+
+#[cfg(feature = "wasm")]
+use wasm_bindgen::prelude::*;
+
+use crate::{circumradius, next_halfedge, prev_halfedge, Delaunator};
+
+/// The boundary of an alpha shape
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct AlphaShape {
+    /// Ids of the triangles kept in the shape (triangle `t` uses the
+    /// halfedges `3 * t`, `3 * t + 1` and `3 * t + 2`)
+    pub triangles: Vec<usize>,
+    /// Outer boundary rings as point indices, winding like the hull
+    pub outer: Vec<Vec<u32>>,
+    /// Boundaries of the holes as point indices, winding opposite to the hull
+    pub holes: Vec<Vec<u32>>,
+}
+
+impl Delaunator {
+    /// Computes the alpha shape of the points
+    ///
+    /// Keeps the triangles whose circumradius is at most `alpha` and returns
+    /// the rings bounding them. Points where several rings meet appear in
+    /// each of them. A zero, negative or NaN `alpha` gives an empty shape.
+    pub fn alpha_shape(&self, alpha: f64) -> AlphaShape {
+        if alpha.is_nan() || alpha <= 0.0 {
+            return AlphaShape::default();
+        }
+
+        let num_triangles = self.triangles.len() / 3;
+        let max_radius = alpha * alpha;
+
+        let kept: Vec<bool> = (0..num_triangles)
+            .map(|t| {
+                let [a, b, c] = [0, 1, 2].map(|i| self.triangles[3 * t + i] as usize);
+                let r = circumradius(
+                    self.coords[2 * a],
+                    self.coords[2 * a + 1],
+                    self.coords[2 * b],
+                    self.coords[2 * b + 1],
+                    self.coords[2 * c],
+                    self.coords[2 * c + 1],
+                );
+                r <= max_radius
+            })
+            .collect();
+
+        let mut shape = AlphaShape {
+            triangles: (0..num_triangles).filter(|&t| kept[t]).collect(),
+            ..AlphaShape::default()
+        };
+
+        // Triangles wind the same way as the hull, so rings around kept
+        // triangles do too, while rings around holes wind the other way
+        for ring in self.boundary_rings(&kept) {
+            if self.ring_area(&ring) > 0.0 {
+                shape.holes.push(ring);
+            } else {
+                shape.outer.push(ring);
+            }
+        }
+
+        shape
+    }
+
+    // Traces the closed boundaries of a set of triangles, walking every
+    // boundary halfedge once in the direction of the triangle winding
+    fn boundary_rings(&self, kept: &[bool]) -> Vec<Vec<u32>> {
+        let is_boundary = |e: usize| {
+            let twin = self.halfedges[e];
+            kept[e / 3] && (twin == -1 || !kept[twin as usize / 3])
+        };
+
+        let mut used = vec![false; self.halfedges.len()];
+        let mut rings = Vec::new();
+
+        for start in 0..self.halfedges.len() {
+            if used[start] || !is_boundary(start) {
+                continue;
+            }
+
+            let mut ring = Vec::new();
+            let mut e = start;
+            loop {
+                used[e] = true;
+                ring.push(self.triangles[e]);
+
+                // Turn around the end point of e through kept triangles until
+                // reaching the next boundary halfedge
+                let mut n = next_halfedge(e);
+                while !is_boundary(n) {
+                    n = next_halfedge(self.halfedges[n] as usize);
+                }
+
+                if n == start {
+                    break;
+                }
+                e = n;
+            }
+
+            rings.push(ring);
+        }
+
+        rings
+    }
+
+    // Shoelace area of a ring of point indices, positive when the ring winds
+    // counterclockwise with the y axis pointing up. Sums are taken relative
+    // to the first point, so that a large offset does not round them away.
+    fn ring_area(&self, ring: &[u32]) -> f64 {
+        let point = |i: u32| (self.coords[2 * i as usize], self.coords[2 * i as usize + 1]);
+        let (ox, oy) = point(ring[0]);
+        let mut area = 0.0;
+        for (k, &a) in ring.iter().enumerate() {
+            let (ax, ay) = point(a);
+            let (bx, by) = point(ring[(k + 1) % ring.len()]);
+            area += (ax - ox) * (by - oy) - (bx - ox) * (ay - oy);
+        }
+        area / 2.0
+    }
+}
+
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+impl Delaunator {
+    /// Computes a concave hull of the points
+    ///
+    /// Implements the chi-shape algorithm: starting from the triangulation,
+    /// the longest boundary edge is repeatedly removed together with its
+    /// triangle, as long as it is longer than `max_edge` and removing it
+    /// keeps the shape a simple polygon. Returns point indices winding like
+    /// `hull`; a `max_edge` of infinity gives back the convex hull, and a
+    /// zero, negative or NaN one erodes as far as the polygon stays simple.
+    #[cfg_attr(feature = "wasm", wasm_bindgen(js_name = "concaveHull"))]
+    pub fn concave_hull(&self, max_edge: f64) -> Vec<u32> {
+        if self.triangles.is_empty() {
+            return self.hull.clone();
+        }
+
+        let mut kept = vec![true; self.triangles.len() / 3];
+        let mut on_boundary = vec![false; self.coords.len() / 2];
+
+        // Squared lengths are non-negative, so their bit patterns sort like
+        // the values themselves
+        let mut queue = BinaryHeap::new();
+        for e in 0..self.halfedges.len() {
+            if self.halfedges[e] == -1 {
+                on_boundary[self.triangles[e] as usize] = true;
+                queue.push((self.edge_length(e).to_bits(), e));
+            }
+        }
+
+        // A NaN max_edge fails the comparison and erodes as far as zero
+        let max_length = if max_edge > 0.0 {
+            max_edge * max_edge
+        } else {
+            0.0
+        };
+
+        while let Some((length, e)) = queue.pop() {
+            if f64::from_bits(length) <= max_length {
+                break;
+            }
+
+            let t = e / 3;
+            if !kept[t] {
+                continue;
+            }
+
+            // Removing a triangle whose third point is already on the
+            // boundary would pinch the polygon
+            let r = self.triangles[prev_halfedge(e)] as usize;
+            if on_boundary[r] {
+                continue;
+            }
+
+            kept[t] = false;
+            on_boundary[r] = true;
+
+            // Both other edges lead to r, which was an interior point, so
+            // they have twins that now lie on the boundary
+            for f in [next_halfedge(e), prev_halfedge(e)] {
+                let twin = self.halfedges[f] as usize;
+                queue.push((self.edge_length(twin).to_bits(), twin));
+            }
+        }
+
+        self.boundary_rings(&kept)
+            .into_iter()
+            .next()
+            .unwrap_or_default()
+    }
+}
@@ -0,0 +1,506 @@
+//! Compact binary file format for triangulations
+//!
+//! [`Delaunator::write_to`] stores a triangulation as a 48-byte header
+//! followed by its arrays in little-endian order, and a CRC-32 of
+//! everything before it:
+//!
+//! | Bytes | Content                                                    |
+//! |-------|------------------------------------------------------------|
+//! | 4     | Magic `DLNR`                                               |
+//! | 2     | Format version, currently 1                                |
+//! | 2     | Flags, bit 0 set for a weighted triangulation              |
+//! | 8 × 5 | Number of points, halfedges, hull points, hidden points and attributes per point |
+//! | …     | `coords`, weights, `triangles`, `halfedges`, `hull`, `hidden` and attributes |
+//! | 4     | CRC-32 (IEEE) of the header and arrays                     |
+//!
+//! Weights are only present in weighted triangulations. Attributes are
+//! per-point values stored along with the triangulation, such as
+//! elevations, as `attributes[i * k + j]` for attribute `j` of point `i`.
+//! Every array is padded with zeros to a multiple of 8 bytes, so that each
+//! starts 8-byte aligned when the data does.
+//!
+//! [`Delaunator::read_from`] reads the arrays back, checking them like
+//! [`Delaunator::from_parts`]. [`View`] instead borrows them in place from
+//! a memory-mapped file or a buffer copied from a JavaScript
+//! `ArrayBuffer`, without copying them, on little-endian targets.
+
+#[cfg(feature = "std")]
+use alloc::vec::Vec;
+use core::fmt;
+use core::mem::size_of;
+
+use bytemuck::Pod;
+#[cfg(feature = "std")]
+use std::io::{self, Read, Write};
+#[cfg(feature = "wasm")]
+use wasm_bindgen::prelude::*;
+
+use crate::{Delaunator, Error};
+
+const MAGIC: [u8; 4] = *b"DLNR";
+const VERSION: u16 = 1;
+// Flag set when the triangulation has weights
+const WEIGHTED: u16 = 1;
+const HEADER_LEN: usize = 48;
+// Elements read at a time, so that a corrupted count cannot allocate more
+// than the data actually holds
+#[cfg(feature = "std")]
+const CHUNK: usize = 1 << 16;
+
+/// Reasons for rejecting the data of a binary triangulation
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FormatError {
+    /// The data does not start with the magic bytes of the format
+    NotTriangulation,
+    /// The data was written in a newer version of the format
+    UnsupportedVersion(u16),
+    /// The data is shorter or longer than its header announces
+    Length,
+    /// The checksum does not match the data
+    Checksum,
+    /// The data does not start at a multiple of 8 bytes, which borrowing
+    /// its arrays requires
+    Misaligned,
+    /// The target is big-endian, so the little-endian arrays cannot be
+    /// borrowed
+    ByteOrder,
+    /// The arrays do not form a valid triangulation
+    Triangulation(Error),
+}
+
+impl fmt::Display for FormatError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FormatError::NotTriangulation => f.write_str("Not a binary triangulation"),
+            FormatError::UnsupportedVersion(version) => {
+                write!(f, "Unsupported format version {version}")
+            }
+            FormatError::Length => f.write_str("Data length does not match its header"),
+            FormatError::Checksum => f.write_str("Checksum mismatch"),
+            FormatError::Misaligned => f.write_str("Data is not 8-byte aligned"),
+            FormatError::ByteOrder => f.write_str("Cannot borrow little-endian arrays"),
+            FormatError::Triangulation(error) => error.fmt(f),
+        }
+    }
+}
+
+impl core::error::Error for FormatError {}
+
+#[cfg(feature = "std")]
+impl From<FormatError> for io::Error {
+    fn from(error: FormatError) -> io::Error {
+        io::Error::new(io::ErrorKind::InvalidData, error)
+    }
+}
+
+// Counts stored in the header
+#[derive(Debug, Clone, Copy)]
+struct Header {
+    flags: u16,
+    points: u64,
+    halfedges: u64,
+    hull: u64,
+    hidden: u64,
+    attributes: u64,
+}
+
+impl Header {
+    #[cfg(feature = "std")]
+    fn new(d: &Delaunator, attributes: usize) -> Header {
+        Header {
+            flags: if d.weights.is_empty() { 0 } else { WEIGHTED },
+            points: (d.coords.len() / 2) as u64,
+            halfedges: d.triangles.len() as u64,
+            hull: d.hull.len() as u64,
+            hidden: d.hidden.len() as u64,
+            attributes: attributes as u64,
+        }
+    }
+
+    #[cfg(feature = "std")]
+    fn encode(&self) -> [u8; HEADER_LEN] {
+        let mut bytes = [0; HEADER_LEN];
+        bytes[..4].copy_from_slice(&MAGIC);
+        bytes[4..6].copy_from_slice(&VERSION.to_le_bytes());
+        bytes[6..8].copy_from_slice(&self.flags.to_le_bytes());
+        let counts = [
+            self.points,
+            self.halfedges,
+            self.hull,
+            self.hidden,
+            self.attributes,
+        ];
+        for (k, count) in counts.iter().enumerate() {
+            bytes[8 + 8 * k..16 + 8 * k].copy_from_slice(&count.to_le_bytes());
+        }
+        bytes
+    }
+
+    fn decode(bytes: &[u8; HEADER_LEN]) -> Result<Header, FormatError> {
+        if bytes[..4] != MAGIC {
+            return Err(FormatError::NotTriangulation);
+        }
+        let version = u16::from_le_bytes([bytes[4], bytes[5]]);
+        if version != VERSION {
+            return Err(FormatError::UnsupportedVersion(version));
+        }
+
+        let count = |k: usize| {
+            let start = 8 + 8 * k;
+            u64::from_le_bytes(bytes[start..start + 8].try_into().unwrap())
+        };
+        Ok(Header {
+            flags: u16::from_le_bytes([bytes[6], bytes[7]]),
+            points: count(0),
+            halfedges: count(1),
+            hull: count(2),
+            hidden: count(3),
+            attributes: count(4),
+        })
+    }
+
+    // Number of elements of each array, in file order, or None if they
+    // could not be addressed
+    fn lengths(&self) -> Option<[usize; 7]> {
+        let points = usize::try_from(self.points).ok()?;
+        let weights = if self.flags & WEIGHTED != 0 {
+            points
+        } else {
+            0
+        };
+        Some([
+            points.checked_mul(2)?,
+            weights,
+            usize::try_from(self.halfedges).ok()?,
+            usize::try_from(self.halfedges).ok()?,
+            usize::try_from(self.hull).ok()?,
+            usize::try_from(self.hidden).ok()?,
+            points.checked_mul(usize::try_from(self.attributes).ok()?)?,
+        ])
+    }
+
+    // Byte offsets of the arrays, then of the checksum
+    fn offsets(&self) -> Option<[usize; 8]> {
+        let lengths = self.lengths()?;
+        let mut offsets = [HEADER_LEN; 8];
+        for k in 0..7 {
+            let bytes = lengths[k].checked_mul(ELEMENT_SIZES[k])?;
+            offsets[k + 1] = offsets[k].checked_add(padded(bytes)?)?;
+        }
+        offsets[7].checked_add(4)?;
+        Some(offsets)
+    }
+}
+
+// Size of the elements of each array, in file order
+const ELEMENT_SIZES: [usize; 7] = [8, 8, 4, 4, 4, 4, 8];
+
+// Rounds a number of bytes up to a multiple of 8
+fn padded(bytes: usize) -> Option<usize> {
+    Some(bytes.checked_add(7)? & !7)
+}
+
+/// Triangulation borrowed from binary data without copying its arrays
+///
+/// The data must start 8-byte aligned, as memory-mapped files and
+/// allocations of `u64` or `f64` do. On big-endian targets, or for
+/// misaligned data, use [`Delaunator::read_from`] instead.
+#[derive(Debug, Clone, Copy)]
+pub struct View<'a> {
+    bytes: &'a [u8],
+    attributes: usize,
+    lengths: [usize; 7],
+    offsets: [usize; 8],
+}
+
+impl<'a> View<'a> {
+    /// Borrows the triangulation stored in `bytes`
+    ///
+    /// Checks the header, length and checksum, but not the triangulation
+    /// itself, which [`View::to_delaunator`] does. Returns error if the
+    /// data is not a binary triangulation, or cannot be borrowed.
+    pub fn new(bytes: &'a [u8]) -> Result<View<'a>, FormatError> {
+        if cfg!(target_endian = "big") {
+            return Err(FormatError::ByteOrder);
+        }
+        if !(bytes.as_ptr() as usize).is_multiple_of(8) {
+            return Err(FormatError::Misaligned);
+        }
+
+        let header: &[u8; HEADER_LEN] = bytes
+            .get(..HEADER_LEN)
+            .ok_or(FormatError::Length)?
+            .try_into()
+            .unwrap();
+        let header = Header::decode(header)?;
+        let lengths = header.lengths().ok_or(FormatError::Length)?;
+        let offsets = header.offsets().ok_or(FormatError::Length)?;
+        if bytes.len() != offsets[7] + 4 {
+            return Err(FormatError::Length);
+        }
+
+        let checksum = u32::from_le_bytes(bytes[offsets[7]..].try_into().unwrap());
+        if crc32(0, &bytes[..offsets[7]]) != checksum {
+            return Err(FormatError::Checksum);
+        }
+
+        Ok(View {
+            bytes,
+            attributes: header.attributes as usize,
+            lengths,
+            offsets,
+        })
+    }
+
+    // Array k, which starts aligned for its element type
+    fn array<T: Pod>(&self, k: usize) -> &'a [T] {
+        let start = self.offsets[k];
+        bytemuck::cast_slice(&self.bytes[start..start + self.lengths[k] * size_of::<T>()])
+    }
+
+    /// Input coordinates `[x0, y0, x1, y1, ...]`
+    pub fn coords(&self) -> &'a [f64] {
+        self.array(0)
+    }
+
+    /// Weights of the points, empty for an unweighted triangulation
+    pub fn weights(&self) -> &'a [f64] {
+        self.array(1)
+    }
+
+    pub fn triangles(&self) -> &'a [u32] {
+        self.array(2)
+    }
+
+    pub fn halfedges(&self) -> &'a [i32] {
+        self.array(3)
+    }
+
+    pub fn hull(&self) -> &'a [u32] {
+        self.array(4)
+    }
+
+    pub fn hidden(&self) -> &'a [u32] {
+        self.array(5)
+    }
+
+    /// Number of attributes stored per point
+    pub fn attribute_count(&self) -> usize {
+        self.attributes
+    }
+
+    /// Attributes of the points, `attribute_count()` values per point
+    pub fn attributes(&self) -> &'a [f64] {
+        self.array(6)
+    }
+
+    /// Copies the arrays into a `Delaunator`, checking them like
+    /// [`Delaunator::from_parts`]
+    pub fn to_delaunator(&self) -> Result<Delaunator, Error> {
+        Delaunator::from_arrays(
+            self.coords().to_vec(),
+            self.weights().to_vec(),
+            self.triangles().to_vec(),
+            self.halfedges().to_vec(),
+            self.hull().to_vec(),
+            self.hidden().to_vec(),
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+impl Delaunator {
+    /// Writes the triangulation in the binary format
+    pub fn write_to<W: Write>(&self, writer: W) -> io::Result<()> {
+        self.write_with_attributes(writer, &[])
+    }
+
+    /// Writes the triangulation in the binary format, with per-point
+    /// attributes stored as `attributes[i * k + j]` for attribute `j` of
+    /// point `i`
+    ///
+    /// Returns error if `attributes` does not hold the same number of
+    /// values for every point, or if writing fails.
+    pub fn write_with_attributes<W: Write>(&self, writer: W, attributes: &[f64]) -> io::Result<()> {
+        let n = self.coords.len() / 2;
+        let count = attributes.len().checked_div(n).unwrap_or(0);
+        if count * n != attributes.len() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "Expected the same number of attributes for every point",
+            ));
+        }
+
+        let mut writer = Checksummed::new(writer);
+        writer.write_all(&Header::new(self, count).encode())?;
+        write_array(&mut writer, &self.coords)?;
+        write_array(&mut writer, &self.weights)?;
+        write_array(&mut writer, &self.triangles)?;
+        write_array(&mut writer, &self.halfedges)?;
+        write_array(&mut writer, &self.hull)?;
+        write_array(&mut writer, &self.hidden)?;
+        write_array(&mut writer, attributes)?;
+
+        let checksum = writer.checksum;
+        writer.inner.write_all(&checksum.to_le_bytes())
+    }
+
+    /// Reads a triangulation in the binary format
+    ///
+    /// Returns an error of kind `InvalidData` wrapping a [`FormatError`] if
+    /// the data is not a valid binary triangulation, checked like
+    /// [`Delaunator::from_parts`]. Attributes are skipped.
+    pub fn read_from<R: Read>(reader: R) -> io::Result<Delaunator> {
+        Ok(Delaunator::read_with_attributes(reader)?.0)
+    }
+
+    /// Same as `read_from`, also returning the per-point attributes
+    pub fn read_with_attributes<R: Read>(reader: R) -> io::Result<(Delaunator, Vec<f64>)> {
+        let mut reader = Checksummed::new(reader);
+        let mut header = [0; HEADER_LEN];
+        reader.read_exact(&mut header)?;
+        let header = Header::decode(&header)?;
+        // The offsets also check that the arrays fit in memory
+        let lengths = header.lengths().ok_or(FormatError::Length)?;
+        header.offsets().ok_or(FormatError::Length)?;
+
+        let coords = read_array(&mut reader, lengths[0])?;
+        let weights = read_array(&mut reader, lengths[1])?;
+        let triangles = read_array(&mut reader, lengths[2])?;
+        let halfedges = read_array(&mut reader, lengths[3])?;
+        let hull = read_array(&mut reader, lengths[4])?;
+        let hidden = read_array(&mut reader, lengths[5])?;
+        let attributes = read_array(&mut reader, lengths[6])?;
+
+        let mut checksum = [0; 4];
+        reader.inner.read_exact(&mut checksum)?;
+        if u32::from_le_bytes(checksum) != reader.checksum {
+            return Err(FormatError::Checksum.into());
+        }
+
+        let delaunator =
+            Delaunator::from_arrays(coords, weights, triangles, halfedges, hull, hidden)
+                .map_err(FormatError::Triangulation)?;
+        Ok((delaunator, attributes))
+    }
+}
+
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+impl Delaunator {
+    /// Encodes the triangulation in the binary format
+    #[wasm_bindgen(js_name = "toBytes")]
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        self.write_to(&mut bytes).unwrap();
+        bytes
+    }
+
+    /// Decodes a triangulation in the binary format, such as the contents
+    /// of an `ArrayBuffer`
+    #[wasm_bindgen(js_name = "fromBytes")]
+    pub fn from_bytes(bytes: &[u8]) -> Result<Delaunator, JsValue> {
+        Delaunator::read_from(bytes).map_err(|error| JsValue::from_str(&error.to_string()))
+    }
+}
+
+// Reader or writer computing the checksum of the bytes that go through it
+#[cfg(feature = "std")]
+struct Checksummed<T> {
+    inner: T,
+    checksum: u32,
+}
+
+#[cfg(feature = "std")]
+impl<T> Checksummed<T> {
+    fn new(inner: T) -> Checksummed<T> {
+        Checksummed { inner, checksum: 0 }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<W: Write> Checksummed<W> {
+    fn write_all(&mut self, bytes: &[u8]) -> io::Result<()> {
+        self.checksum = crc32(self.checksum, bytes);
+        self.inner.write_all(bytes)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<R: Read> Checksummed<R> {
+    fn read_exact(&mut self, bytes: &mut [u8]) -> io::Result<()> {
+        self.inner.read_exact(bytes)?;
+        self.checksum = crc32(self.checksum, bytes);
+        Ok(())
+    }
+}
+
+// Writes an array in little-endian order, padded to a multiple of 8 bytes
+#[cfg(feature = "std")]
+fn write_array<T: Pod, W: Write>(writer: &mut Checksummed<W>, array: &[T]) -> io::Result<()> {
+    if cfg!(target_endian = "little") {
+        writer.write_all(bytemuck::cast_slice(array))?;
+    } else {
+        for chunk in array.chunks(CHUNK) {
+            let mut bytes = bytemuck::cast_slice::<T, u8>(chunk).to_vec();
+            bytes
+                .chunks_exact_mut(size_of::<T>())
+                .for_each(|b| b.reverse());
+            writer.write_all(&bytes)?;
+        }
+    }
+
+    let bytes = core::mem::size_of_val(array);
+    writer.write_all(&[0; 8][..padded(bytes).unwrap() - bytes])
+}
+
+// Reads an array of len elements written by `write_array`
+#[cfg(feature = "std")]
+fn read_array<T: Pod, R: Read>(reader: &mut Checksummed<R>, len: usize) -> io::Result<Vec<T>> {
+    let mut array = Vec::new();
+    while array.len() < len {
+        let start = array.len();
+        array.resize(start + CHUNK.min(len - start), T::zeroed());
+        let bytes = bytemuck::cast_slice_mut::<T, u8>(&mut array[start..]);
+        reader.read_exact(bytes)?;
+        if cfg!(target_endian = "big") {
+            bytes
+                .chunks_exact_mut(size_of::<T>())
+                .for_each(|b| b.reverse());
+        }
+    }
+
+    let bytes = len * size_of::<T>();
+    let mut padding = [0; 8];
+    reader.read_exact(&mut padding[..padded(bytes).unwrap() - bytes])?;
+    Ok(array)
+}
+
+// Table of the CRC-32 remainders of every byte, for the reflected IEEE
+// polynomial
+const CRC_TABLE: [u32; 256] = {
+    let mut table = [0; 256];
+    let mut i = 0;
+    while i < 256 {
+        let mut crc = i as u32;
+        let mut bit = 0;
+        while bit < 8 {
+            crc = if crc & 1 != 0 {
+                0xEDB8_8320 ^ (crc >> 1)
+            } else {
+                crc >> 1
+            };
+            bit += 1;
+        }
+        table[i] = crc;
+        i += 1;
+    }
+    table
+};
+
+// Continues the CRC-32 `crc` of some data over `bytes`
+fn crc32(crc: u32, bytes: &[u8]) -> u32 {
+    !bytes.iter().fold(!crc, |crc, &b| {
+        CRC_TABLE[((crc ^ b as u32) & 0xFF) as usize] ^ (crc >> 8)
+    })
+}
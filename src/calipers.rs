@@ -0,0 +1,343 @@
+//! Rotating calipers analytics on a convex hull
+//!
+//! Every function takes the point coordinates and a convex hull given as
+//! point indices, such as [`Delaunator::hull`](crate::Delaunator) or the
+//! output of [`convex_hull`](crate::hull::convex_hull), in either winding.
+//!
+//! The farthest pair, the width and the optimal oriented rectangles are all
+//! found among a linear number of candidates by rotating a set of parallel
+//! supporting lines (calipers) around the hull: the minimum-area and
+//! minimum-perimeter rectangles always have a side along a hull edge, so it
+//! is enough to track the extreme points in the direction of each edge and
+//! of its normal, which only ever move forward along the hull.
+
+use alloc::{vec, vec::Vec};
+
+use num_traits::Float;
+
+use crate::{circumcenter, dist};
+
+/// A rectangle with arbitrary orientation
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct OrientedRect {
+    pub center: (f64, f64),
+    /// Length of the side along `angle`
+    pub width: f64,
+    /// Length of the side across `angle`
+    pub height: f64,
+    /// Direction of the width side, in radians from the x axis
+    pub angle: f64,
+}
+
+impl OrientedRect {
+    pub fn area(&self) -> f64 {
+        self.width * self.height
+    }
+
+    pub fn perimeter(&self) -> f64 {
+        2.0 * (self.width + self.height)
+    }
+
+    /// Returns the corners of the rectangle, in counterclockwise order with
+    /// the y axis pointing up
+    pub fn corners(&self) -> [(f64, f64); 4] {
+        let (cos, sin) = (Float::cos(self.angle), Float::sin(self.angle));
+        let (ux, uy) = (cos * self.width / 2.0, sin * self.width / 2.0);
+        let (vx, vy) = (-sin * self.height / 2.0, cos * self.height / 2.0);
+        let (cx, cy) = self.center;
+        [
+            (cx - ux - vx, cy - uy - vy),
+            (cx + ux - vx, cy + uy - vy),
+            (cx + ux + vx, cy + uy + vy),
+            (cx - ux + vx, cy - uy + vy),
+        ]
+    }
+}
+
+/// A circle given by its center and radius
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Circle {
+    pub x: f64,
+    pub y: f64,
+    pub r: f64,
+}
+
+/// Finds the two hull points farthest from each other
+///
+/// Returns their indices and the distance between them, or `None` for an
+/// empty hull.
+pub fn diameter(coords: &[f64], hull: &[u32]) -> Option<(u32, u32, f64)> {
+    let points = hull_points(coords, hull);
+    let h = points.len();
+    if h < 3 || signed_area(&points) == 0.0 {
+        let (a, b) = extremes(&points)?;
+        return Some((
+            hull[a],
+            hull[b],
+            Float::sqrt(dist(points[a].0, points[a].1, points[b].0, points[b].1)),
+        ));
+    }
+
+    // For each edge, advance to the point farthest from its line; the
+    // antipodal pairs met along the way include the farthest pair
+    let mut best = (0, 0, 0.0);
+    let mut j = 1;
+    for i in 0..h {
+        let (a, b) = (points[i], points[(i + 1) % h]);
+        for _ in 0..h {
+            let next = (j + 1) % h;
+            if triangle_area(a, b, points[next]) > triangle_area(a, b, points[j]) {
+                j = next;
+            } else {
+                break;
+            }
+        }
+        for k in [i, (i + 1) % h] {
+            let d = dist(points[k].0, points[k].1, points[j].0, points[j].1);
+            if d > best.2 {
+                best = (k, j, d);
+            }
+        }
+    }
+
+    Some((hull[best.0], hull[best.1], Float::sqrt(best.2)))
+}
+
+/// Computes the minimum width of the hull
+///
+/// This is the smallest distance between two parallel lines enclosing all
+/// the points, 0 for collinear points.
+pub fn width(coords: &[f64], hull: &[u32]) -> f64 {
+    edge_rects(&hull_points(coords, hull))
+        .into_iter()
+        .map(|r| r.height)
+        .min_by(f64::total_cmp)
+        .unwrap_or(0.0)
+}
+
+/// Computes the oriented rectangle of minimum area enclosing the hull
+///
+/// Returns `None` for an empty hull. Collinear points give a rectangle of
+/// zero height along their line.
+pub fn min_area_rect(coords: &[f64], hull: &[u32]) -> Option<OrientedRect> {
+    edge_rects(&hull_points(coords, hull))
+        .into_iter()
+        .min_by(|a, b| a.area().total_cmp(&b.area()))
+}
+
+/// Computes the oriented rectangle of minimum perimeter enclosing the hull
+///
+/// Returns `None` for an empty hull. Collinear points give a rectangle of
+/// zero height along their line.
+pub fn min_perimeter_rect(coords: &[f64], hull: &[u32]) -> Option<OrientedRect> {
+    edge_rects(&hull_points(coords, hull))
+        .into_iter()
+        .min_by(|a, b| a.perimeter().total_cmp(&b.perimeter()))
+}
+
+/// Computes the smallest circle enclosing the hull
+///
+/// Runs Welzl's algorithm over the hull points, which is enough since the
+/// circle only touches hull points. Returns `None` for an empty hull.
+pub fn min_enclosing_circle(coords: &[f64], hull: &[u32]) -> Option<Circle> {
+    let mut points = hull_points(coords, hull);
+    if points.is_empty() {
+        return None;
+    }
+
+    // Welzl's algorithm runs in expected linear time on points in random
+    // order; a fixed seed keeps the result reproducible
+    let mut seed = 0x2545f4914f6cdd1d_u64;
+    for i in (1..points.len()).rev() {
+        seed ^= seed << 13;
+        seed ^= seed >> 7;
+        seed ^= seed << 17;
+        points.swap(i, (seed % (i as u64 + 1)) as usize);
+    }
+
+    let mut circle = point_circle(points[0]);
+    for i in 1..points.len() {
+        if contains(circle, points[i]) {
+            continue;
+        }
+        circle = point_circle(points[i]);
+        for j in 0..i {
+            if contains(circle, points[j]) {
+                continue;
+            }
+            circle = diametral_circle(points[i], points[j]);
+            for k in 0..j {
+                if !contains(circle, points[k]) {
+                    circle = circumscribed_circle(points[i], points[j], points[k]);
+                }
+            }
+        }
+    }
+
+    Some(circle)
+}
+
+fn hull_points(coords: &[f64], hull: &[u32]) -> Vec<(f64, f64)> {
+    hull.iter()
+        .map(|&i| (coords[2 * i as usize], coords[2 * i as usize + 1]))
+        .collect()
+}
+
+// Shoelace area, positive when the points wind counterclockwise with the y
+// axis pointing up, summed relative to the first point so that a large
+// offset does not round the area away
+fn signed_area(points: &[(f64, f64)]) -> f64 {
+    let Some(&o) = points.first() else {
+        return 0.0;
+    };
+    let mut area = 0.0;
+    for (k, a) in points.iter().enumerate() {
+        let b = points[(k + 1) % points.len()];
+        area += (a.0 - o.0) * (b.1 - o.1) - (b.0 - o.0) * (a.1 - o.1);
+    }
+    area / 2.0
+}
+
+// Twice the unsigned area of the triangle abc
+fn triangle_area(a: (f64, f64), b: (f64, f64), c: (f64, f64)) -> f64 {
+    ((b.0 - a.0) * (c.1 - a.1) - (b.1 - a.1) * (c.0 - a.0)).abs()
+}
+
+// Farthest pair of collinear points: the point farthest from any point is an
+// end of the line, and the point farthest from that end is the other end
+fn extremes(points: &[(f64, f64)]) -> Option<(usize, usize)> {
+    let farthest = |from: (f64, f64)| {
+        (0..points.len()).max_by(|&i, &j| {
+            dist(from.0, from.1, points[i].0, points[i].1).total_cmp(&dist(
+                from.0,
+                from.1,
+                points[j].0,
+                points[j].1,
+            ))
+        })
+    };
+    let a = farthest(*points.first()?)?;
+    let b = farthest(points[a])?;
+    Some((a, b))
+}
+
+// The rectangle with a side along each hull edge, found by rotating
+// calipers that track the extreme points along the edge and across it
+fn edge_rects(points: &[(f64, f64)]) -> Vec<OrientedRect> {
+    let h = points.len();
+    let area = signed_area(points);
+
+    if h < 3 || area == 0.0 {
+        let Some((a, b)) = extremes(points) else {
+            return Vec::new();
+        };
+        let (pa, pb) = (points[a], points[b]);
+        return vec![OrientedRect {
+            center: ((pa.0 + pb.0) / 2.0, (pa.1 + pb.1) / 2.0),
+            width: Float::sqrt(dist(pa.0, pa.1, pb.0, pb.1)),
+            height: 0.0,
+            angle: Float::atan2(pb.1 - pa.1, pb.0 - pa.0),
+        }];
+    }
+
+    // Normals point into the hull, to the left of the edges when the hull
+    // winds counterclockwise with the y axis pointing up
+    let side = area.signum();
+    let frame = |i: usize| {
+        let (a, b) = (points[i], points[(i + 1) % h]);
+        let len = Float::sqrt(dist(a.0, a.1, b.0, b.1));
+        let u = ((b.0 - a.0) / len, (b.1 - a.1) / len);
+        (a, u, (-u.1 * side, u.0 * side))
+    };
+    let along = |a: (f64, f64), dir: (f64, f64), k: usize| {
+        (points[k].0 - a.0) * dir.0 + (points[k].1 - a.1) * dir.1
+    };
+
+    // Start each caliper at its extreme point for the first edge
+    let (a, u, n) = frame(0);
+    let extreme = |f: &dyn Fn(usize) -> f64| (0..h).max_by(|&i, &j| f(i).total_cmp(&f(j))).unwrap();
+    let mut far = extreme(&|k| along(a, n, k));
+    let mut front = extreme(&|k| along(a, u, k));
+    let mut back = extreme(&|k| -along(a, u, k));
+
+    let mut rects = Vec::with_capacity(h);
+    for i in 0..h {
+        let (a, u, n) = frame(i);
+        let advance = |mut k: usize, f: &dyn Fn(usize) -> f64| {
+            for _ in 0..h {
+                let next = (k + 1) % h;
+                if f(next) > f(k) {
+                    k = next;
+                } else {
+                    break;
+                }
+            }
+            k
+        };
+        far = advance(far, &|k| along(a, n, k));
+        front = advance(front, &|k| along(a, u, k));
+        back = advance(back, &|k| -along(a, u, k));
+
+        let height = along(a, n, far);
+        let (lo, hi) = (along(a, u, back), along(a, u, front));
+        let mid = (lo + hi) / 2.0;
+        rects.push(OrientedRect {
+            center: (
+                a.0 + u.0 * mid + n.0 * height / 2.0,
+                a.1 + u.1 * mid + n.1 * height / 2.0,
+            ),
+            width: hi - lo,
+            height,
+            angle: Float::atan2(u.1, u.0),
+        });
+    }
+
+    rects
+}
+
+fn point_circle(p: (f64, f64)) -> Circle {
+    Circle {
+        x: p.0,
+        y: p.1,
+        r: 0.0,
+    }
+}
+
+fn diametral_circle(a: (f64, f64), b: (f64, f64)) -> Circle {
+    Circle {
+        x: (a.0 + b.0) / 2.0,
+        y: (a.1 + b.1) / 2.0,
+        r: Float::sqrt(dist(a.0, a.1, b.0, b.1)) / 2.0,
+    }
+}
+
+// Circle through three points, or the diametral circle of the farthest two
+// when they are collinear
+fn circumscribed_circle(a: (f64, f64), b: (f64, f64), c: (f64, f64)) -> Circle {
+    let (x, y) = circumcenter(a.0, a.1, b.0, b.1, c.0, c.1);
+    if x.is_finite() && y.is_finite() {
+        let r = Float::sqrt(
+            [a, b, c]
+                .iter()
+                .map(|p| dist(x, y, p.0, p.1))
+                .fold(0.0, f64::max),
+        );
+        return Circle { x, y, r };
+    }
+
+    [
+        diametral_circle(a, b),
+        diametral_circle(b, c),
+        diametral_circle(a, c),
+    ]
+    .into_iter()
+    .max_by(|p, q| p.r.total_cmp(&q.r))
+    .unwrap()
+}
+
+// Containment with a small relative tolerance, so that points on the circle
+// found by rounding are not reported outside
+fn contains(circle: Circle, p: (f64, f64)) -> bool {
+    let d = Float::sqrt(dist(circle.x, circle.y, p.0, p.1));
+    d <= circle.r * (1.0 + 1e-12) + f64::EPSILON * (circle.x.abs() + circle.y.abs())
+}
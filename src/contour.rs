@@ -0,0 +1,362 @@
+//! Contour extraction on triangulated scalar fields
+//!
+//! Isolines are traced directly on the triangulation: every triangle crossed
+//! by a level contributes one segment, and segments are stitched into
+//! continuous polylines by stepping through `halfedges`. Filled contours
+//! (bands between two levels) are built by clipping every triangle to the
+//! band and keeping the parts of the clipped polygons that lie on the band
+//! boundary.
+//!
+//! Points on triangle edges are always interpolated from the endpoint with
+//! the lower index, so lines and bands sharing an edge crossing get exactly
+//! the same coordinates.
+
+use alloc::{collections::BTreeMap, vec, vec::Vec};
+
+use crate::{next_halfedge, Delaunator};
+
+/// A polyline traced along a single contour level
+#[derive(Debug, Clone, PartialEq)]
+pub struct Isoline {
+    /// Line vertices as `[x0, y0, x1, y1, ...]`
+    pub coords: Vec<f64>,
+    /// Whether the line is a closed loop
+    ///
+    /// The first point of a closed line is not repeated at its end.
+    /// Open lines start and end on the convex hull.
+    pub closed: bool,
+}
+
+/// All the isolines of a single level
+#[derive(Debug, Clone, PartialEq)]
+pub struct Contour {
+    pub level: f64,
+    pub lines: Vec<Isoline>,
+}
+
+/// The region where values lie between two consecutive levels
+#[derive(Debug, Clone, PartialEq)]
+pub struct Band {
+    pub lower: f64,
+    pub upper: f64,
+    /// Boundary rings as `[x0, y0, x1, y1, ...]`, without repeating the
+    /// first point
+    ///
+    /// Outer rings wind in the same direction as the hull and holes wind
+    /// in the opposite direction.
+    pub rings: Vec<Vec<f64>>,
+}
+
+// Identifies a point of a contour independently of the triangle it was
+// computed from: either an input point or the crossing of a level with the
+// edge between two input points (lower index first).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum Key {
+    Vertex(u32),
+    Edge(u32, u32, usize),
+}
+
+impl Delaunator {
+    /// Extracts isolines of per-point values at the given levels
+    ///
+    /// `values` holds one value per input point. A point counts as above a
+    /// level when its value is greater than or equal to it. Lines are
+    /// oriented so that higher values always lie on the same side, the
+    /// right-hand side when the y axis points up (left-hand on screen).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `values` does not contain exactly one value per point.
+    pub fn contours(&self, values: &[f64], levels: &[f64]) -> Vec<Contour> {
+        self.check_values(values);
+
+        let num_triangles = self.triangles.len() / 3;
+        let mut visited = vec![false; num_triangles];
+
+        levels
+            .iter()
+            .map(|&level| {
+                visited.fill(false);
+                let mut lines = Vec::new();
+
+                // Open lines start on a hull edge, so trace those first
+                for t in 0..num_triangles {
+                    if let Some((entry, _)) = self.crossing(values, level, t) {
+                        if self.halfedges[entry] == -1 {
+                            lines.push(self.trace_isoline(values, level, t, &mut visited));
+                        }
+                    }
+                }
+
+                // Whatever is left forms closed loops
+                for t in 0..num_triangles {
+                    if !visited[t] && self.crossing(values, level, t).is_some() {
+                        lines.push(self.trace_isoline(values, level, t, &mut visited));
+                    }
+                }
+
+                Contour { level, lines }
+            })
+            .collect()
+    }
+
+    /// Extracts filled contours of per-point values
+    ///
+    /// Returns one [`Band`] for each pair of consecutive `levels`, which are
+    /// expected in increasing order. A band covers values greater than or
+    /// equal to its lower level and below its upper level, except for the
+    /// last band which also includes its upper level.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `values` does not contain exactly one value per point.
+    pub fn filled_contours(&self, values: &[f64], levels: &[f64]) -> Vec<Band> {
+        self.check_values(values);
+
+        levels
+            .windows(2)
+            .enumerate()
+            .map(|(i, pair)| Band {
+                lower: pair[0],
+                upper: pair[1],
+                rings: self.band_rings(values, levels, i),
+            })
+            .collect()
+    }
+
+    fn check_values(&self, values: &[f64]) {
+        assert_eq!(
+            values.len(),
+            self.coords.len() / 2,
+            "expected one value per point"
+        );
+    }
+
+    // Returns the entry and exit halfedges of the level line in triangle t.
+    // The line enters through the edge going from above to below the level
+    // and leaves through the edge going from below to above, so the exit
+    // halfedge of a triangle is the twin of the entry of the next one.
+    fn crossing(&self, values: &[f64], level: f64, t: usize) -> Option<(usize, usize)> {
+        let mut entry = None;
+        let mut exit = None;
+
+        for e in 3 * t..3 * t + 3 {
+            let above_start = values[self.triangles[e] as usize] >= level;
+            let above_end = values[self.triangles[next_halfedge(e)] as usize] >= level;
+            if above_start && !above_end {
+                entry = Some(e);
+            } else if !above_start && above_end {
+                exit = Some(e);
+            }
+        }
+
+        Some((entry?, exit?))
+    }
+
+    // Follows a level line from triangle t until it leaves the hull or
+    // comes back to t
+    fn trace_isoline(&self, values: &[f64], level: f64, t: usize, visited: &mut [bool]) -> Isoline {
+        let mut coords = Vec::new();
+        let mut t = t;
+
+        let Some((start, _)) = self.crossing(values, level, t) else {
+            return Isoline {
+                coords,
+                closed: false,
+            };
+        };
+        let (x, y) = self.edge_point(values, level, start);
+        coords.extend_from_slice(&[x, y]);
+
+        let mut closed = false;
+        loop {
+            visited[t] = true;
+            let Some((_, exit)) = self.crossing(values, level, t) else {
+                break;
+            };
+
+            let twin = self.halfedges[exit];
+            if twin != -1 && twin as usize == start {
+                closed = true;
+                break;
+            }
+
+            // Lines through a point lying exactly on the level cross several
+            // edges at the same place
+            let (x, y) = self.edge_point(values, level, exit);
+            if coords[coords.len() - 2..] != [x, y] {
+                coords.extend_from_slice(&[x, y]);
+            }
+
+            if twin == -1 || visited[twin as usize / 3] {
+                break;
+            }
+            t = twin as usize / 3;
+        }
+
+        // A line through points lying exactly on the level can come back to
+        // its first point before the triangle it started in
+        let n = coords.len();
+        if n > 4 && coords[..2] == coords[n - 2..] {
+            coords.truncate(n - 2);
+            closed = true;
+        }
+
+        Isoline { coords, closed }
+    }
+
+    // Interpolates the crossing of a level along the edge of halfedge e
+    fn edge_point(&self, values: &[f64], level: f64, e: usize) -> (f64, f64) {
+        let a = self.triangles[e];
+        let b = self.triangles[next_halfedge(e)];
+        self.interpolate(values, level, a.min(b) as usize, a.max(b) as usize)
+    }
+
+    fn interpolate(&self, values: &[f64], level: f64, a: usize, b: usize) -> (f64, f64) {
+        let (ax, ay) = (self.coords[2 * a], self.coords[2 * a + 1]);
+        let (bx, by) = (self.coords[2 * b], self.coords[2 * b + 1]);
+
+        // A point lying exactly on the level is returned as is, since
+        // ax + 1 * (bx - ax) may round away from bx and leave a near copy
+        // of the point in the line
+        if values[b] == level {
+            return (bx, by);
+        }
+        let t = (level - values[a]) / (values[b] - values[a]);
+        (ax + t * (bx - ax), ay + t * (by - ay))
+    }
+
+    fn key_point(&self, values: &[f64], levels: &[f64], key: Key) -> (f64, f64) {
+        match key {
+            Key::Vertex(i) => (self.coords[2 * i as usize], self.coords[2 * i as usize + 1]),
+            Key::Edge(a, b, l) => self.interpolate(values, levels[l], a as usize, b as usize),
+        }
+    }
+
+    // Builds the boundary rings of the band between levels[l] and levels[l + 1]
+    fn band_rings(&self, values: &[f64], levels: &[f64], l: usize) -> Vec<Vec<f64>> {
+        let (lower, upper) = (levels[l], levels[l + 1]);
+        let last = l + 2 == levels.len();
+        let below = |v: f64| v < lower;
+        let above = |v: f64| v > upper || (!last && v == upper);
+        let in_band = |v: f64| !below(v) && !above(v);
+
+        // Directed boundary segments of the band
+        let mut segments: Vec<(Key, Key)> = Vec::new();
+        let mut polygon: Vec<(Key, Option<usize>)> = Vec::with_capacity(6);
+
+        for t in 0..self.triangles.len() / 3 {
+            // Clip the triangle to the band by walking its edges and keeping
+            // the part of each edge whose values lie within the band. Each
+            // polygon vertex remembers the halfedge it starts a segment along.
+            polygon.clear();
+            for e in 3 * t..3 * t + 3 {
+                let a = self.triangles[e];
+                let b = self.triangles[next_halfedge(e)];
+                let (va, vb) = (values[a as usize], values[b as usize]);
+
+                // Point where the edge crosses levels[k], which is one of its
+                // endpoints when that endpoint lies exactly on the level
+                let cross = |k: usize| {
+                    if va == levels[k] {
+                        Key::Vertex(a)
+                    } else if vb == levels[k] {
+                        Key::Vertex(b)
+                    } else {
+                        Key::Edge(a.min(b), a.max(b), k)
+                    }
+                };
+
+                let start = if in_band(va) {
+                    Key::Vertex(a)
+                } else if below(va) && !below(vb) {
+                    cross(l)
+                } else if above(va) && !above(vb) {
+                    cross(l + 1)
+                } else {
+                    continue;
+                };
+
+                let end = if in_band(vb) {
+                    Key::Vertex(b)
+                } else if above(vb) {
+                    cross(l + 1)
+                } else {
+                    cross(l)
+                };
+
+                push_vertex(&mut polygon, start, Some(e));
+                push_vertex(&mut polygon, end, None);
+            }
+
+            // Close the polygon, the merged vertex keeps the segment of the
+            // first one
+            while polygon.len() > 1 && polygon[0].0 == polygon[polygon.len() - 1].0 {
+                polygon.pop();
+            }
+            if polygon.len() < 2 {
+                continue;
+            }
+
+            for i in 0..polygon.len() {
+                let (from, along) = polygon[i];
+                let to = polygon[(i + 1) % polygon.len()].0;
+
+                // Segments along an edge shared with another triangle are
+                // covered by that triangle as well and cancel out
+                if let Some(e) = along {
+                    if self.halfedges[e] != -1 {
+                        continue;
+                    }
+                }
+                segments.push((from, to));
+            }
+        }
+
+        // Stitch the segments into rings
+        let mut outgoing: BTreeMap<Key, Vec<usize>> = BTreeMap::new();
+        for (i, &(from, _)) in segments.iter().enumerate() {
+            outgoing.entry(from).or_default().push(i);
+        }
+
+        let mut used = vec![false; segments.len()];
+        let mut rings = Vec::new();
+
+        for first in 0..segments.len() {
+            if used[first] {
+                continue;
+            }
+
+            let mut ring = Vec::new();
+            let mut s = first;
+            loop {
+                used[s] = true;
+                let (from, to) = segments[s];
+                let (x, y) = self.key_point(values, levels, from);
+                ring.extend_from_slice(&[x, y]);
+
+                let next = outgoing
+                    .get(&to)
+                    .and_then(|candidates| candidates.iter().copied().find(|&c| !used[c]));
+                match next {
+                    Some(n) => s = n,
+                    None => break,
+                }
+            }
+
+            rings.push(ring);
+        }
+
+        rings
+    }
+}
+
+// Appends a vertex to a clipped polygon. Repeated vertices are merged and
+// keep the segment starting at the later one, since the segment between
+// them is empty.
+fn push_vertex(polygon: &mut Vec<(Key, Option<usize>)>, key: Key, along: Option<usize>) {
+    match polygon.last_mut() {
+        Some(last) if last.0 == key => last.1 = along,
+        _ => polygon.push((key, along)),
+    }
+}
@@ -0,0 +1,562 @@
+//! Delaunay tetrahedralization of 3D points
+//!
+//! The output follows the same flat-array layout as the planar
+//! triangulation. Tetrahedron `t` is made of the points
+//! `tetrahedra[4 * t..4 * t + 4]`, and its face opposite to corner `k` is
+//! the *halfface* `4 * t + k`. `halffaces[f]` is the matching halfface of
+//! the tetrahedron on the other side of face `f`, or -1 on the convex hull,
+//! just like `halfedges` for triangles.
+//!
+//! Every tetrahedron has a positive orientation: its fourth point lies below
+//! the plane of the first three, seen counterclockwise from above. The
+//! construction is an incremental Bowyer-Watson algorithm over points sorted
+//! along a Morton curve, with ghost tetrahedra joining each hull face to a
+//! virtual point at infinity. Orientation and in-sphere tests use exact
+//! arithmetic, so the result is valid for any input, including grids and
+//! other degenerate point sets.
+
+use alloc::{vec, vec::Vec};
+
+use num_traits::Float;
+use robust::{insphere, orient3d, Coord3D};
+#[cfg(feature = "wasm")]
+use wasm_bindgen::prelude::*;
+
+use crate::Error;
+
+// Virtual point at infinity, a corner of ghost tetrahedra
+const INFINITE: u32 = u32::MAX;
+
+// Corners of the face opposite to each corner of a tetrahedron, ordered so
+// that the face winds counterclockwise seen from outside the tetrahedron
+const FACES: [[usize; 3]; 4] = [[1, 3, 2], [0, 2, 3], [0, 3, 1], [0, 1, 2]];
+
+/// Delaunay tetrahedralization of a set of 3D points
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+#[derive(Debug)]
+pub struct Delaunay3d {
+    // Input coordinates [x0, y0, z0, x1, y1, z1, ...]
+    pub(crate) coords: Vec<f64>,
+
+    #[cfg_attr(feature = "wasm", wasm_bindgen(skip))]
+    pub tetrahedra: Vec<u32>,
+    #[cfg_attr(feature = "wasm", wasm_bindgen(skip))]
+    pub halffaces: Vec<i32>,
+    /// Convex hull as triangles, winding counterclockwise seen from outside
+    #[cfg_attr(feature = "wasm", wasm_bindgen(skip))]
+    pub hull: Vec<u32>,
+}
+
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+impl Delaunay3d {
+    /// Tetrahedralizes a flat array of point coordinates
+    ///
+    /// The input format is `[x0, y0, z0, x1, y1, z1, ...]`. Duplicate points
+    /// are left out, and points that are all coplanar give no tetrahedra.
+    /// Returns error if the input is invalid.
+    #[cfg_attr(feature = "wasm", wasm_bindgen(constructor))]
+    pub fn new(coords: Vec<f64>) -> Result<Delaunay3d, Error> {
+        if coords.is_empty() || !coords.len().is_multiple_of(3) {
+            return Err(Error::InvalidCoordinates);
+        }
+
+        if coords.iter().any(|v| !v.is_finite()) {
+            return Err(Error::NonFiniteCoordinate);
+        }
+
+        if !predicates_fit(&coords) {
+            return Err(Error::CoordinateRange);
+        }
+
+        // The point at infinity takes the last u32 index
+        if coords.len() / 3 >= INFINITE as usize {
+            return Err(Error::TooManyPoints);
+        }
+
+        let mut delaunay = Delaunay3d {
+            coords,
+            tetrahedra: Vec::new(),
+            halffaces: Vec::new(),
+            hull: Vec::new(),
+        };
+        delaunay.update()?;
+
+        Ok(delaunay)
+    }
+
+    /// Updates the tetrahedralization when points have been modified in-place
+    ///
+    /// Returns an error if the points can no longer be tetrahedralized, and
+    /// leaves the tetrahedralization empty.
+    #[cfg_attr(feature = "wasm", wasm_bindgen(js_name = "update"))]
+    pub fn update(&mut self) -> Result<(), Error> {
+        self.tetrahedra = Vec::new();
+        self.halffaces = Vec::new();
+        self.hull = Vec::new();
+
+        let mut order = morton_order(&self.coords);
+        let Some(seeds) = find_seeds(&self.coords, &order) else {
+            return Ok(());
+        };
+        order.retain(|&i| !seeds.contains(&i));
+
+        let mut mesh = Mesh::new(&self.coords, seeds);
+        for &i in &order {
+            mesh.insert(i)?;
+        }
+
+        // Output halffaces index every slot, ghosts included, as i32
+        if mesh.tetrahedra.len() > i32::MAX as usize {
+            return Err(Error::TooManyPoints);
+        }
+        (self.tetrahedra, self.halffaces, self.hull) = mesh.extract();
+        Ok(())
+    }
+}
+
+// JavaScript API methods for web use
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+impl Delaunay3d {
+    /// Get tetrahedralization result as array of indices
+    ///
+    /// Returns a Uint32Array containing indices that form tetrahedra
+    /// (each group of 4 values represents a tetrahedron)
+    #[wasm_bindgen(getter, js_name = "tetrahedra")]
+    pub fn get_tetrahedra(&self) -> js_sys::Uint32Array {
+        let array = js_sys::Uint32Array::new_with_length(self.tetrahedra.len() as u32);
+        array.copy_from(&self.tetrahedra);
+        array
+    }
+
+    /// Get halffaces result as array
+    ///
+    /// Returns an Int32Array containing halfface indices
+    /// that allow traversal of the tetrahedralization
+    #[wasm_bindgen(getter, js_name = "halffaces")]
+    pub fn get_halffaces(&self) -> js_sys::Int32Array {
+        let array = js_sys::Int32Array::new_with_length(self.halffaces.len() as u32);
+        array.copy_from(&self.halffaces);
+        array
+    }
+
+    /// Get convex hull result as array
+    ///
+    /// Returns a Uint32Array containing indices of points forming
+    /// the triangles of the convex hull (3 values per triangle)
+    #[wasm_bindgen(getter, js_name = "hull")]
+    pub fn get_hull(&self) -> js_sys::Uint32Array {
+        let array = js_sys::Uint32Array::new_with_length(self.hull.len() as u32);
+        array.copy_from(&self.hull);
+        array
+    }
+
+    /// Get input coordinates as array
+    ///
+    /// Returns a Float64Array containing the input coordinates
+    /// in the format [x0, y0, z0, x1, y1, z1, ...]
+    #[wasm_bindgen(getter, js_name = "coords")]
+    pub fn get_coords(&self) -> js_sys::Float64Array {
+        let array = js_sys::Float64Array::new_with_length(self.coords.len() as u32);
+        array.copy_from(&self.coords);
+        array
+    }
+}
+
+impl Delaunay3d {
+    /// Returns the points of halfface `f`
+    ///
+    /// The face winds counterclockwise when seen from outside of the
+    /// tetrahedron it belongs to.
+    pub fn face(&self, f: usize) -> [u32; 3] {
+        let t = f / 4;
+        FACES[f % 4].map(|k| self.tetrahedra[4 * t + k])
+    }
+}
+
+// Tetrahedralization under construction. Every halfface has a twin thanks
+// to the ghost tetrahedra, and tetrahedra removed by an insertion leave free
+// slots that later insertions reuse.
+struct Mesh<'a> {
+    coords: &'a [f64],
+
+    tetrahedra: Vec<u32>,
+    halffaces: Vec<usize>,
+    free: Vec<usize>,
+
+    // Tetrahedron the next point location starts from
+    last: usize,
+    // Rotates the face tested first by the walk, which can otherwise cycle
+    walk_turn: usize,
+
+    // Per tetrahedron, the last insertion that tested it, times two, plus
+    // one when it conflicted with the inserted point
+    marks: Vec<u32>,
+    stamp: u32,
+
+    // Scratch buffers reused by every insertion
+    cavity: Vec<usize>,
+    stack: Vec<usize>,
+    boundary: Vec<([u32; 3], usize)>,
+    // Faces of new tetrahedra waiting for their twin, keyed by the edge
+    // they share with the cavity boundary
+    pending: Vec<(u32, u32, usize)>,
+}
+
+impl<'a> Mesh<'a> {
+    // Starts from the tetrahedron of four non-coplanar points and the four
+    // ghost tetrahedra around it
+    fn new(coords: &'a [f64], seeds: [u32; 4]) -> Self {
+        let n = coords.len() / 3;
+        let [a, b, mut c, mut d] = seeds;
+
+        let mut mesh = Mesh {
+            coords,
+            tetrahedra: Vec::with_capacity(28 * n),
+            halffaces: Vec::with_capacity(28 * n),
+            free: Vec::new(),
+            last: 0,
+            walk_turn: 0,
+            marks: Vec::with_capacity(7 * n),
+            stamp: 0,
+            cavity: Vec::new(),
+            stack: Vec::new(),
+            boundary: Vec::new(),
+            pending: Vec::new(),
+        };
+
+        if mesh.orient(a, b, c, d) < 0.0 {
+            core::mem::swap(&mut c, &mut d);
+        }
+        mesh.tetrahedra.extend_from_slice(&[a, b, c, d]);
+        mesh.halffaces.extend_from_slice(&[0; 4]);
+        mesh.marks.push(0);
+
+        // Ghost k lies across face k of the first tetrahedron, with the face
+        // reversed so that the point at infinity is below it
+        for k in 0..4 {
+            let [x, y, z] = mesh.face(0, k);
+            let g = mesh.allocate();
+            mesh.tetrahedra[4 * g..4 * g + 4].copy_from_slice(&[x, z, y, INFINITE]);
+            mesh.halffaces[4 * g + 3] = k;
+            mesh.halffaces[k] = 4 * g + 3;
+            mesh.link_pending(g, INFINITE);
+        }
+
+        mesh
+    }
+
+    fn point(&self, i: u32) -> Coord3D<f64> {
+        let i = i as usize;
+        Coord3D {
+            x: self.coords[3 * i],
+            y: self.coords[3 * i + 1],
+            z: self.coords[3 * i + 2],
+        }
+    }
+
+    fn orient(&self, a: u32, b: u32, c: u32, d: u32) -> f64 {
+        orient3d(self.point(a), self.point(b), self.point(c), self.point(d))
+    }
+
+    // Position of the point at infinity in tetrahedron t, if it is a ghost
+    fn infinite_corner(&self, t: usize) -> Option<usize> {
+        (0..4).find(|&k| self.tetrahedra[4 * t + k] == INFINITE)
+    }
+
+    fn face(&self, t: usize, k: usize) -> [u32; 3] {
+        FACES[k].map(|j| self.tetrahedra[4 * t + j])
+    }
+
+    // Fails when the new tetrahedra do not close up around the point, which
+    // only wrong predicate results can cause
+    fn insert(&mut self, i: u32) -> Result<(), Error> {
+        let t = self.locate(i);
+        if !self.conflicts(t, i) {
+            // Duplicate of a point already in the mesh
+            return Ok(());
+        }
+
+        // Grow the cavity of tetrahedra whose circumsphere contains the new
+        // point and collect the faces on its boundary
+        self.stamp += 1;
+        let (tested, conflicted) = (2 * self.stamp, 2 * self.stamp + 1);
+        self.cavity.clear();
+        self.boundary.clear();
+        self.marks[t] = conflicted;
+        self.cavity.push(t);
+        self.stack.push(t);
+
+        while let Some(t) = self.stack.pop() {
+            for k in 0..4 {
+                let outside = self.halffaces[4 * t + k];
+                let o = outside / 4;
+                let mark = self.marks[o];
+                let inside = if mark == conflicted {
+                    true
+                } else if mark == tested {
+                    false
+                } else if self.conflicts(o, i) {
+                    self.marks[o] = conflicted;
+                    self.cavity.push(o);
+                    self.stack.push(o);
+                    true
+                } else {
+                    self.marks[o] = tested;
+                    false
+                };
+
+                if !inside {
+                    self.boundary.push((self.face(t, k), outside));
+                }
+            }
+        }
+        self.free.extend_from_slice(&self.cavity);
+
+        // Join every boundary face to the new point. The cavity is star
+        // shaped around it, so each new tetrahedron keeps the orientation
+        // of the one it replaces, and new tetrahedra sharing a boundary edge
+        // are glued together.
+        self.pending.clear();
+        for k in 0..self.boundary.len() {
+            let ([x, y, z], outside) = self.boundary[k];
+            let t = self.allocate();
+            self.tetrahedra[4 * t..4 * t + 4].copy_from_slice(&[x, y, z, i]);
+            self.halffaces[4 * t + 3] = outside;
+            self.halffaces[outside] = 4 * t + 3;
+            self.link_pending(t, i);
+            self.last = t;
+        }
+
+        if !self.pending.is_empty() {
+            return Err(Error::InvalidTriangulation);
+        }
+        Ok(())
+    }
+
+    // Pairs the faces of tetrahedron t that contain the given apex with the
+    // faces of other new tetrahedra sharing the same edge
+    fn link_pending(&mut self, t: usize, apex: u32) {
+        for k in 0..4 {
+            let [x, y, z] = self.face(t, k);
+            let (u, v) = if x == apex {
+                (y, z)
+            } else if y == apex {
+                (z, x)
+            } else if z == apex {
+                (x, y)
+            } else {
+                continue;
+            };
+
+            let key = (u.min(v), u.max(v));
+            match self.pending.iter().position(|p| (p.0, p.1) == key) {
+                Some(p) => {
+                    let f = self.pending.swap_remove(p).2;
+                    self.halffaces[4 * t + k] = f;
+                    self.halffaces[f] = 4 * t + k;
+                }
+                None => self.pending.push((key.0, key.1, 4 * t + k)),
+            }
+        }
+    }
+
+    fn allocate(&mut self) -> usize {
+        match self.free.pop() {
+            Some(t) => t,
+            None => {
+                self.tetrahedra.extend_from_slice(&[0; 4]);
+                self.halffaces.extend_from_slice(&[0; 4]);
+                self.marks.push(0);
+                self.marks.len() - 1
+            }
+        }
+    }
+
+    // Finds the tetrahedron containing point i, or a ghost whose hull face
+    // has i strictly outside, by walking across the faces i lies beyond
+    fn locate(&mut self, i: u32) -> usize {
+        let p = self.point(i);
+        let mut t = self.last;
+        if let Some(k) = self.infinite_corner(t) {
+            t = self.halffaces[4 * t + k] / 4;
+        }
+
+        'walk: loop {
+            self.walk_turn = (self.walk_turn + 1) % 4;
+            for j in 0..4 {
+                let k = (self.walk_turn + j) % 4;
+                let [a, b, c] = self.face(t, k);
+                if orient3d(self.point(a), self.point(b), self.point(c), p) < 0.0 {
+                    t = self.halffaces[4 * t + k] / 4;
+                    if self.infinite_corner(t).is_some() {
+                        return t;
+                    }
+                    continue 'walk;
+                }
+            }
+            return t;
+        }
+    }
+
+    // Whether inserting point i removes tetrahedron t
+    fn conflicts(&self, t: usize, i: u32) -> bool {
+        let p = self.point(i);
+
+        let Some(k) = self.infinite_corner(t) else {
+            let [a, b, c, d] = [0, 1, 2, 3].map(|j| self.point(self.tetrahedra[4 * t + j]));
+            return insphere(a, b, c, d, p) > 0.0;
+        };
+
+        // A ghost conflicts with the points beyond its hull face. Points in
+        // the plane of the face conflict with it when they lie inside the
+        // circumcircle of the face, which is where they are inside the
+        // circumsphere of the tetrahedron on the other side.
+        let [a, b, c] = self.face(t, k);
+        let side = orient3d(self.point(a), self.point(b), self.point(c), p);
+        if side != 0.0 {
+            return side > 0.0;
+        }
+        self.conflicts(self.halffaces[4 * t + k] / 4, i)
+    }
+
+    // Compacts the finite tetrahedra into the output arrays and collects the
+    // hull faces
+    fn extract(&self) -> (Vec<u32>, Vec<i32>, Vec<u32>) {
+        let num_slots = self.tetrahedra.len() / 4;
+        let mut is_free = vec![false; num_slots];
+        for &t in &self.free {
+            is_free[t] = true;
+        }
+
+        let mut ids = vec![usize::MAX; num_slots];
+        let mut count = 0;
+        for t in 0..num_slots {
+            if !is_free[t] && self.infinite_corner(t).is_none() {
+                ids[t] = count;
+                count += 1;
+            }
+        }
+
+        let mut tetrahedra = Vec::with_capacity(4 * count);
+        let mut halffaces = Vec::with_capacity(4 * count);
+        let mut hull = Vec::new();
+        for t in 0..num_slots {
+            if ids[t] == usize::MAX {
+                continue;
+            }
+            tetrahedra.extend_from_slice(&self.tetrahedra[4 * t..4 * t + 4]);
+            for k in 0..4 {
+                let twin = self.halffaces[4 * t + k];
+                let id = ids[twin / 4];
+                if id == usize::MAX {
+                    halffaces.push(-1);
+                    hull.extend_from_slice(&self.face(t, k));
+                } else {
+                    halffaces.push((4 * id + twin % 4) as i32);
+                }
+            }
+        }
+
+        (tetrahedra, halffaces, hull)
+    }
+}
+
+// The in-sphere test multiplies five coordinate differences together, so the
+// spread of the points must neither overflow nor underflow to zero when
+// raised to the fifth power
+fn predicates_fit(coords: &[f64]) -> bool {
+    let mut min = [f64::INFINITY; 3];
+    let mut max = [f64::NEG_INFINITY; 3];
+    for p in coords.chunks_exact(3) {
+        for k in 0..3 {
+            min[k] = min[k].min(p[k]);
+            max[k] = max[k].max(p[k]);
+        }
+    }
+
+    let spread = (0..3).map(|k| max[k] - min[k]).fold(0.0, f64::max);
+    spread == 0.0 || (Float::powi(spread, 5).is_finite() && Float::powi(spread, 5) > 0.0)
+}
+
+// Picks four non-coplanar points: the first two distinct points of the
+// insertion order, the point farthest from the line through them, then the
+// first point off the plane of all three
+fn find_seeds(coords: &[f64], order: &[u32]) -> Option<[u32; 4]> {
+    let point = |i: u32| {
+        let i = i as usize;
+        [coords[3 * i], coords[3 * i + 1], coords[3 * i + 2]]
+    };
+
+    let a = *order.first()?;
+    let pa = point(a);
+    let b = *order.iter().find(|&&i| point(i) != pa)?;
+    let pb = point(b);
+
+    let ab = [pb[0] - pa[0], pb[1] - pa[1], pb[2] - pa[2]];
+    let spread = |i: u32| {
+        let p = point(i);
+        let ap = [p[0] - pa[0], p[1] - pa[1], p[2] - pa[2]];
+        let n = [
+            ab[1] * ap[2] - ab[2] * ap[1],
+            ab[2] * ap[0] - ab[0] * ap[2],
+            ab[0] * ap[1] - ab[1] * ap[0],
+        ];
+        n[0] * n[0] + n[1] * n[1] + n[2] * n[2]
+    };
+    let c = *order
+        .iter()
+        .max_by(|&&i, &&j| spread(i).total_cmp(&spread(j)))?;
+
+    let coord = |i: u32| {
+        let [x, y, z] = point(i);
+        Coord3D { x, y, z }
+    };
+    let d = *order
+        .iter()
+        .find(|&&i| orient3d(coord(a), coord(b), coord(c), coord(i)) != 0.0)?;
+
+    Some([a, b, c, d])
+}
+
+// Sorts point indices along a Morton (Z-order) curve over the bounding box,
+// so that consecutive points are close to each other
+fn morton_order(coords: &[f64]) -> Vec<u32> {
+    let mut min = [f64::INFINITY; 3];
+    let mut max = [f64::NEG_INFINITY; 3];
+    for p in coords.chunks_exact(3) {
+        for k in 0..3 {
+            min[k] = min[k].min(p[k]);
+            max[k] = max[k].max(p[k]);
+        }
+    }
+
+    let side = ((1u32 << 10) - 1) as f64;
+    let scale = [0, 1, 2].map(|k| {
+        if max[k] > min[k] {
+            side / (max[k] - min[k])
+        } else {
+            0.0
+        }
+    });
+
+    let mut keyed: Vec<(u32, u32)> = coords
+        .chunks_exact(3)
+        .enumerate()
+        .map(|(i, p)| {
+            let [x, y, z] = [0, 1, 2].map(|k| spread_bits(((p[k] - min[k]) * scale[k]) as u32));
+            (x | (y << 1) | (z << 2), i as u32)
+        })
+        .collect();
+    keyed.sort_unstable();
+    keyed.into_iter().map(|(_, i)| i).collect()
+}
+
+// Spreads the low 10 bits of v so that two zero bits follow each of them
+fn spread_bits(v: u32) -> u32 {
+    let mut v = v & 0x3ff;
+    v = (v | (v << 16)) & 0x030000ff;
+    v = (v | (v << 8)) & 0x0300f00f;
+    v = (v | (v << 4)) & 0x030c30c3;
+    (v | (v << 2)) & 0x09249249
+}
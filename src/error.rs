@@ -0,0 +1,55 @@
+//! Errors returned by the triangulation constructors
+
+use core::fmt;
+
+#[cfg(feature = "wasm")]
+use wasm_bindgen::JsValue;
+
+/// Reasons for rejecting the input of a triangulation
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Error {
+    /// The coordinates array does not hold a whole number of points
+    InvalidCoordinates,
+    /// A coordinate is NaN or infinite
+    NonFiniteCoordinate,
+    /// The points are spread too far apart, or too close together, for the
+    /// geometric predicates to be evaluated in floating point, or their
+    /// weights are too large
+    CoordinateRange,
+    /// There is not exactly one finite weight per point
+    InvalidWeights,
+    /// The periodic domain does not have a positive finite width and height
+    InvalidDomain,
+    /// Some empty circle is as wide as the periodic domain
+    TooSparse,
+    /// The point or halfedge indices would not fit in the index type
+    TooManyPoints,
+    /// The arrays given for a triangulation break one of its invariants, or
+    /// the construction failed to keep them
+    InvalidTriangulation,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Error::InvalidCoordinates => "Invalid coordinates array",
+            Error::NonFiniteCoordinate => "Expected coords to contain numbers",
+            Error::CoordinateRange => "Coordinates spread over a range too wide or too narrow",
+            Error::InvalidWeights => "Expected one weight per point",
+            Error::InvalidDomain => "Expected a positive width and height",
+            Error::TooSparse => "Too few points for a periodic triangulation",
+            Error::TooManyPoints => "Too many points for the index type",
+            Error::InvalidTriangulation => "Arrays do not form a valid triangulation",
+        })
+    }
+}
+
+impl core::error::Error for Error {}
+
+// Thrown as a JavaScript string with the same message
+#[cfg(feature = "wasm")]
+impl From<Error> for JsValue {
+    fn from(error: Error) -> JsValue {
+        JsValue::from_str(&error.to_string())
+    }
+}
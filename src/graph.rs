@@ -0,0 +1,305 @@
+//! Proximity graphs derived from the triangulation
+//!
+//! The Euclidean minimum spanning tree, the relative neighborhood graph,
+//! the Urquhart graph and the Gabriel graph are all subgraphs of the
+//! Delaunay triangulation:
+//!
+//! EMST ⊆ RNG ⊆ Urquhart ⊆ Gabriel ⊆ Delaunay
+//!
+//! so each of them is obtained by filtering the Delaunay edges instead of
+//! looking at every pair of points. Graphs are returned as flat edge lists
+//! `[a0, b0, a1, b1, ...]` of point indices, each edge appearing once.
+//!
+//! Collinear points, and pairs of points, have no triangles. Every graph is
+//! then the path through them in order along their line. Duplicate points
+//! are left out of the triangulation, so only one point of each set of
+//! coincident points has edges, and the spanning tree spans the distinct
+//! positions.
+
+use alloc::{vec, vec::Vec};
+
+#[cfg(feature = "wasm")]
+use wasm_bindgen::prelude::*;
+
+use crate::{dist, next_halfedge, prev_halfedge, Delaunator};
+
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+impl Delaunator {
+    /// Returns every edge of the triangulation
+    #[cfg_attr(feature = "wasm", wasm_bindgen(js_name = "edges"))]
+    pub fn edges(&self) -> Vec<u32> {
+        if self.triangles.is_empty() {
+            return self.hull_path().flatten().collect();
+        }
+
+        let mut edges = Vec::new();
+        for e in 0..self.halfedges.len() {
+            if self.halfedges[e] < e as i32 {
+                edges.push(self.triangles[e]);
+                edges.push(self.triangles[next_halfedge(e)]);
+            }
+        }
+        edges
+    }
+
+    /// Computes the Euclidean minimum spanning tree
+    ///
+    /// Runs Kruskal's algorithm over the Delaunay edges. Edges are returned
+    /// in order of increasing length.
+    #[cfg_attr(feature = "wasm", wasm_bindgen(js_name = "euclideanMst"))]
+    pub fn euclidean_mst(&self) -> Vec<u32> {
+        if self.triangles.is_empty() {
+            let length = |&[a, b]: &[u32; 2]| {
+                let (a, b) = (a as usize, b as usize);
+                dist(
+                    self.coords[2 * a],
+                    self.coords[2 * a + 1],
+                    self.coords[2 * b],
+                    self.coords[2 * b + 1],
+                )
+            };
+            let mut path: Vec<[u32; 2]> = self.hull_path().collect();
+            path.sort_by(|a, b| length(a).total_cmp(&length(b)));
+            return path.into_iter().flatten().collect();
+        }
+
+        let mut candidates: Vec<usize> = (0..self.halfedges.len())
+            .filter(|&e| self.halfedges[e] < e as i32)
+            .collect();
+        candidates.sort_by(|&a, &b| self.edge_length(a).total_cmp(&self.edge_length(b)));
+
+        let mut parent: Vec<u32> = (0..(self.coords.len() / 2) as u32).collect();
+        let mut tree = Vec::new();
+
+        for e in candidates {
+            let a = self.triangles[e];
+            let b = self.triangles[next_halfedge(e)];
+            let (ra, rb) = (find_root(&mut parent, a), find_root(&mut parent, b));
+            if ra != rb {
+                parent[ra as usize] = rb;
+                tree.push(a);
+                tree.push(b);
+            }
+        }
+
+        tree
+    }
+
+    /// Computes the Gabriel graph
+    ///
+    /// Keeps the edges whose diametral circle contains no other point. For a
+    /// Delaunay edge it is enough to check the opposite corners of its two
+    /// triangles, which must not see the edge under an obtuse angle.
+    #[cfg_attr(feature = "wasm", wasm_bindgen(js_name = "gabrielGraph"))]
+    pub fn gabriel_graph(&self) -> Vec<u32> {
+        if self.triangles.is_empty() {
+            return self.hull_path().flatten().collect();
+        }
+
+        let mut edges = Vec::new();
+        for e in 0..self.halfedges.len() {
+            if self.halfedges[e] < e as i32 && self.is_gabriel(e) {
+                edges.push(self.triangles[e]);
+                edges.push(self.triangles[next_halfedge(e)]);
+            }
+        }
+        edges
+    }
+
+    /// Computes the relative neighborhood graph
+    ///
+    /// Keeps the edges `pq` with no point `r` closer to both `p` and `q`
+    /// than they are to each other. Candidates are the Gabriel edges, and
+    /// the lune of each one is searched by a walk over the Delaunay graph
+    /// from `p` that never leaves the circle of radius `|pq|` around it.
+    /// Every point inside that circle is reachable this way, so the test is
+    /// exact, and the walk only visits the close neighborhood of the edge.
+    #[cfg_attr(feature = "wasm", wasm_bindgen(js_name = "relativeNeighborhoodGraph"))]
+    pub fn relative_neighborhood_graph(&self) -> Vec<u32> {
+        if self.triangles.is_empty() {
+            return self.hull_path().flatten().collect();
+        }
+
+        let inedges = self.inedges();
+        let mut visited = vec![usize::MAX; self.coords.len() / 2];
+        let mut queue = Vec::new();
+        let mut neighbors = Vec::new();
+        let mut edges = Vec::new();
+
+        for e in 0..self.halfedges.len() {
+            if self.halfedges[e] >= e as i32 || !self.is_gabriel(e) {
+                continue;
+            }
+
+            let p = self.triangles[e] as usize;
+            let q = self.triangles[next_halfedge(e)] as usize;
+            let (px, py) = (self.coords[2 * p], self.coords[2 * p + 1]);
+            let (qx, qy) = (self.coords[2 * q], self.coords[2 * q + 1]);
+            let radius = dist(px, py, qx, qy);
+
+            // Visited points are stamped with the edge being tested
+            visited[p] = e;
+            queue.clear();
+            queue.push(p as u32);
+            let mut empty = true;
+
+            'search: while let Some(v) = queue.pop() {
+                self.neighbors(&inedges, v as usize, &mut neighbors);
+                for &r in &neighbors {
+                    let r = r as usize;
+                    if visited[r] == e {
+                        continue;
+                    }
+                    visited[r] = e;
+
+                    let (rx, ry) = (self.coords[2 * r], self.coords[2 * r + 1]);
+                    if dist(px, py, rx, ry) >= radius {
+                        continue;
+                    }
+                    if dist(qx, qy, rx, ry) < radius {
+                        empty = false;
+                        break 'search;
+                    }
+                    queue.push(r as u32);
+                }
+            }
+
+            if empty {
+                edges.push(p as u32);
+                edges.push(q as u32);
+            }
+        }
+
+        edges
+    }
+
+    /// Computes the Urquhart graph
+    ///
+    /// Removes the longest edge of every triangle. Ties between edges of
+    /// equal length are broken by halfedge index so that the result does
+    /// not depend on which side an edge is looked at from.
+    #[cfg_attr(feature = "wasm", wasm_bindgen(js_name = "urquhartGraph"))]
+    pub fn urquhart_graph(&self) -> Vec<u32> {
+        if self.triangles.is_empty() {
+            return self.hull_path().flatten().collect();
+        }
+
+        let mut removed = vec![false; self.halfedges.len()];
+
+        for t in 0..self.triangles.len() / 3 {
+            let longest = (3 * t..3 * t + 3)
+                .max_by(|&a, &b| {
+                    self.edge_length(a)
+                        .total_cmp(&self.edge_length(b))
+                        .then(self.edge_id(a).cmp(&self.edge_id(b)))
+                })
+                .unwrap();
+            removed[self.edge_id(longest)] = true;
+        }
+
+        let mut edges = Vec::new();
+        for e in 0..self.halfedges.len() {
+            if self.halfedges[e] < e as i32 && !removed[self.edge_id(e)] {
+                edges.push(self.triangles[e]);
+                edges.push(self.triangles[next_halfedge(e)]);
+            }
+        }
+        edges
+    }
+}
+
+impl Delaunator {
+    // Consecutive hull points, which are joined by the only edges when there
+    // are no triangles: the hull then lists the distinct collinear points
+    // along their line. Fewer than three points are listed as given, so
+    // pairs of coincident points are skipped.
+    fn hull_path(&self) -> impl Iterator<Item = [u32; 2]> + '_ {
+        let point = |i: u32| (self.coords[2 * i as usize], self.coords[2 * i as usize + 1]);
+        self.hull
+            .windows(2)
+            .filter(move |e| point(e[0]) != point(e[1]))
+            .map(|e| [e[0], e[1]])
+    }
+
+    // For every point, a halfedge ending at it. Hull points get the incoming
+    // hull halfedge so that walking around them covers all their neighbors.
+    // Points left out of the triangulation (duplicates) get -1.
+    pub(crate) fn inedges(&self) -> Vec<i32> {
+        let mut inedges = vec![-1; self.coords.len() / 2];
+        for e in 0..self.halfedges.len() {
+            let p = self.triangles[next_halfedge(e)] as usize;
+            if self.halfedges[e] == -1 || inedges[p] == -1 {
+                inedges[p] = e as i32;
+            }
+        }
+        inedges
+    }
+
+    // Collects the points connected to point i by an edge of the
+    // triangulation, in the order of the triangle winding
+    pub(crate) fn neighbors(&self, inedges: &[i32], i: usize, out: &mut Vec<u32>) {
+        out.clear();
+
+        let e0 = inedges[i];
+        if e0 == -1 {
+            return;
+        }
+
+        let mut e = e0 as usize;
+        loop {
+            out.push(self.triangles[e]);
+            let outgoing = next_halfedge(e);
+            let twin = self.halfedges[outgoing];
+            if twin == -1 {
+                // Reached the hull, the last neighbor is across the outgoing
+                // hull edge
+                out.push(self.triangles[next_halfedge(outgoing)]);
+                return;
+            }
+            e = twin as usize;
+            if e == e0 as usize {
+                return;
+            }
+        }
+    }
+
+    // Whether no point lies strictly inside the diametral circle of the edge
+    // of halfedge e, judged from the opposite corners of its triangles
+    fn is_gabriel(&self, e: usize) -> bool {
+        let p = self.triangles[e] as usize;
+        let q = self.triangles[next_halfedge(e)] as usize;
+        let twin = self.halfedges[e];
+
+        let sees_obtuse = |r: usize| {
+            let (rx, ry) = (self.coords[2 * r], self.coords[2 * r + 1]);
+            let (ax, ay) = (self.coords[2 * p] - rx, self.coords[2 * p + 1] - ry);
+            let (bx, by) = (self.coords[2 * q] - rx, self.coords[2 * q + 1] - ry);
+            ax * bx + ay * by < 0.0
+        };
+
+        if sees_obtuse(self.triangles[prev_halfedge(e)] as usize) {
+            return false;
+        }
+        twin == -1 || !sees_obtuse(self.triangles[prev_halfedge(twin as usize)] as usize)
+    }
+
+    // Identifies an edge by the lower of its two halfedges
+    fn edge_id(&self, e: usize) -> usize {
+        let twin = self.halfedges[e];
+        if twin != -1 && (twin as usize) < e {
+            twin as usize
+        } else {
+            e
+        }
+    }
+}
+
+// Union-find lookup with path halving
+fn find_root(parent: &mut [u32], mut i: u32) -> u32 {
+    while parent[i as usize] != i {
+        let grandparent = parent[parent[i as usize] as usize];
+        parent[i as usize] = grandparent;
+        i = grandparent;
+    }
+    i
+}
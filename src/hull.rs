@@ -0,0 +1,151 @@
+//! Convex hull without triangulation
+//!
+//! For callers that only need the hull, [`convex_hull`] skips building the
+//! triangles and runs Andrew's monotone chain algorithm instead, in
+//! `O(n log n)` time with a single sort. The helpers below measure a hull
+//! given as point indices, either from [`convex_hull`] or
+//! [`Delaunator::hull`](crate::Delaunator).
+
+use alloc::vec::Vec;
+
+use num_traits::Float;
+#[cfg(feature = "wasm")]
+use wasm_bindgen::prelude::*;
+
+use crate::orient2d;
+
+/// Computes the convex hull of a flat array of point coordinates
+///
+/// The input format is `[x0, y0, x1, y1, ...]`. Returns the indices of the
+/// hull points winding in the same direction as `Delaunator::hull`,
+/// starting from the leftmost point (the lowest one on ties). Points lying
+/// on a hull edge are left out, and so are duplicates. When all points are
+/// collinear, returns them ordered along the line like `Delaunator::hull`.
+#[cfg_attr(feature = "wasm", wasm_bindgen(js_name = "convexHull"))]
+pub fn convex_hull(coords: &[f64]) -> Vec<u32> {
+    let n = coords.len() / 2;
+    let point = |i: u32| (coords[2 * i as usize], coords[2 * i as usize + 1]);
+
+    let mut ids: Vec<u32> = (0..n as u32).collect();
+    ids.sort_by(|&a, &b| {
+        let (pa, pb) = (point(a), point(b));
+        pa.0.total_cmp(&pb.0).then(pa.1.total_cmp(&pb.1))
+    });
+    ids.dedup_by(|a, b| point(*a) == point(*b));
+
+    if ids.len() < 3 {
+        return ids;
+    }
+
+    // A chain turns the way the hull winds as long as every point is on the
+    // inner side of the last edge
+    let turns = |chain: &[u32], i: u32| {
+        let (a, b, p) = (
+            point(chain[chain.len() - 2]),
+            point(chain[chain.len() - 1]),
+            point(i),
+        );
+        orient2d(a.0, a.1, b.0, b.1, p.0, p.1) > 0.0
+    };
+
+    // Upper chain from left to right, then lower chain back to the left
+    let mut hull: Vec<u32> = Vec::with_capacity(ids.len() + 1);
+    for &i in &ids {
+        while hull.len() >= 2 && !turns(&hull, i) {
+            hull.pop();
+        }
+        hull.push(i);
+    }
+    let upper = hull.len();
+    for &i in ids.iter().rev().skip(1) {
+        while hull.len() > upper && !turns(&hull, i) {
+            hull.pop();
+        }
+        hull.push(i);
+    }
+    hull.pop();
+
+    if hull.len() < 3 {
+        // Every point is on the line between the two extremes
+        return ids;
+    }
+    hull
+}
+
+/// Computes the area enclosed by a hull given as point indices
+pub fn hull_area(coords: &[f64], hull: &[u32]) -> f64 {
+    signed_area(coords, hull).abs()
+}
+
+/// Computes the perimeter of a hull given as point indices
+pub fn hull_perimeter(coords: &[f64], hull: &[u32]) -> f64 {
+    if hull.len() < 2 {
+        return 0.0;
+    }
+
+    edges(coords, hull)
+        .map(|((ax, ay), (bx, by))| Float::hypot(bx - ax, by - ay))
+        .sum()
+}
+
+/// Computes the centroid of the region enclosed by a hull
+///
+/// Falls back to the centroid of the hull boundary when the hull encloses
+/// no area, as happens for collinear points. Returns NaN coordinates for
+/// an empty hull.
+pub fn hull_centroid(coords: &[f64], hull: &[u32]) -> (f64, f64) {
+    let Some(&first) = hull.first() else {
+        return (f64::NAN, f64::NAN);
+    };
+    let (ox, oy) = (coords[2 * first as usize], coords[2 * first as usize + 1]);
+
+    let area = signed_area(coords, hull);
+    if area != 0.0 {
+        let (mut cx, mut cy) = (0.0, 0.0);
+        for ((ax, ay), (bx, by)) in edges(coords, hull) {
+            let cross = ax * by - bx * ay;
+            cx += (ax + bx) * cross;
+            cy += (ay + by) * cross;
+        }
+        return (ox + cx / (6.0 * area), oy + cy / (6.0 * area));
+    }
+
+    // Average of the edge midpoints weighted by edge length
+    let (mut cx, mut cy, mut length) = (0.0, 0.0, 0.0);
+    for ((ax, ay), (bx, by)) in edges(coords, hull) {
+        let l = Float::hypot(bx - ax, by - ay);
+        cx += (ax + bx) / 2.0 * l;
+        cy += (ay + by) / 2.0 * l;
+        length += l;
+    }
+    if length > 0.0 {
+        (ox + cx / length, oy + cy / length)
+    } else {
+        (ox, oy)
+    }
+}
+
+// Shoelace area, positive when the hull winds counterclockwise with the y
+// axis pointing up
+fn signed_area(coords: &[f64], hull: &[u32]) -> f64 {
+    edges(coords, hull)
+        .map(|((ax, ay), (bx, by))| ax * by - bx * ay)
+        .sum::<f64>()
+        / 2.0
+}
+
+// Iterates over the closed sequence of hull edges as pairs of points
+// relative to the first one, so that the products of the shoelace formula
+// do not lose the size of the hull to a large offset
+fn edges<'a>(
+    coords: &'a [f64],
+    hull: &'a [u32],
+) -> impl Iterator<Item = ((f64, f64), (f64, f64))> + 'a {
+    let point = move |i: u32| (coords[2 * i as usize], coords[2 * i as usize + 1]);
+    let (ox, oy) = hull.first().map_or((0.0, 0.0), |&i| point(i));
+    let relative = move |i: u32| {
+        let (x, y) = point(i);
+        (x - ox, y - oy)
+    };
+    (0..hull.len()).map(move |k| (relative(hull[k]), relative(hull[(k + 1) % hull.len()])))
+}
@@ -0,0 +1,104 @@
+//! Index types of the output arrays
+//!
+//! [`Delaunator`](crate::Delaunator) stores its output as `u32` point
+//! indices and `i32` halfedges, which the JavaScript API exposes directly.
+//! [`Triangulation`] runs the same sweep with any [`Index`] type instead:
+//! `u16` halves the memory of small meshes, and `u64` or `usize` lift the
+//! limit of about 357 million points set by 32-bit halfedges. Inputs whose
+//! indices would not fit are rejected with [`Error::TooManyPoints`] rather
+//! than wrapping around.
+
+use alloc::vec::Vec;
+use core::fmt;
+use core::hash::Hash;
+
+use crate::sweep::Sweep;
+use crate::{check_coords, Error, Sort};
+
+/// Unsigned integer type used for point and halfedge indices
+pub trait Index: Copy + Ord + Hash + fmt::Debug + Send + Sync + 'static {
+    /// Largest value of the type, which is never an index and marks
+    /// halfedges without opposite
+    const NONE: Self;
+
+    /// Converts an index known to fit in the type
+    fn from_usize(i: usize) -> Self;
+
+    fn to_usize(self) -> usize;
+}
+
+macro_rules! impl_index {
+    ($($t:ty),*) => {
+        $(
+            impl Index for $t {
+                const NONE: Self = <$t>::MAX;
+
+                #[inline]
+                fn from_usize(i: usize) -> Self {
+                    i as $t
+                }
+
+                #[inline]
+                fn to_usize(self) -> usize {
+                    self as usize
+                }
+            }
+        )*
+    };
+}
+
+impl_index!(u16, u32, u64, usize);
+
+/// Whether the indices of every point and halfedge of a triangulation of
+/// `n` points stay below `limit`
+pub(crate) fn fits(n: usize, limit: u128) -> bool {
+    let halfedges = 3 * (2 * n as u128).saturating_sub(5);
+    n as u128 <= limit && halfedges <= limit
+}
+
+/// Delaunay triangulation with indices of type `I`
+///
+/// Same as [`Delaunator`](crate::Delaunator) without weights, except that
+/// `halfedges` marks edges without opposite with [`Index::NONE`] instead
+/// of -1.
+#[derive(Debug)]
+pub struct Triangulation<I: Index> {
+    // Input coordinates [x0, y0, x1, y1, ...]
+    coords: Vec<f64>,
+
+    pub triangles: Vec<I>,
+    pub halfedges: Vec<I>,
+    pub hull: Vec<I>,
+}
+
+impl<I: Index> Triangulation<I> {
+    /// Triangulates a flat array of point coordinates `[x0, y0, x1, y1, ...]`
+    ///
+    /// Fewer than 3 points give no triangles. Returns error if the input is
+    /// invalid or if there are too many points for `I`.
+    pub fn new(coords: Vec<f64>) -> Result<Self, Error> {
+        Triangulation::with_sort(coords, Sort::default())
+    }
+
+    /// Same as `new`, sorting the points with `sort`
+    pub fn with_sort(coords: Vec<f64>, sort: Sort) -> Result<Self, Error> {
+        check_coords(&coords)?;
+        if !fits(coords.len() / 2, I::NONE.to_usize() as u128) {
+            return Err(Error::TooManyPoints);
+        }
+
+        let mut sweep = Sweep::new(coords.len() / 2);
+        sweep.run(&coords, sort);
+        Ok(Triangulation {
+            coords,
+            triangles: sweep.triangles,
+            halfedges: sweep.halfedges,
+            hull: sweep.hull,
+        })
+    }
+
+    /// Input coordinates `[x0, y0, x1, y1, ...]`
+    pub fn coords(&self) -> &[f64] {
+        &self.coords
+    }
+}
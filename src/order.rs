@@ -0,0 +1,166 @@
+//! Spatially coherent orders of the points
+//!
+//! Sorting the points along a Hilbert curve puts points that are close in
+//! the plane close in memory. The incremental construction inserts points
+//! in this order, or in the biased randomized insertion order (BRIO) of
+//! Amenta, Choi and Rote, which shuffles points into rounds of doubling
+//! size to avoid the worst cases of a fixed order while keeping each round
+//! along the curve. [`Delaunator::renumber`] applies the same order to the
+//! output, so that the corners of nearby triangles are stored together.
+
+use alloc::{vec, vec::Vec};
+
+#[cfg(feature = "wasm")]
+use wasm_bindgen::prelude::*;
+
+use crate::Delaunator;
+
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+impl Delaunator {
+    /// Renumbers the points along a Hilbert curve
+    ///
+    /// Reorders the points, and with them `coords`, the weights and every
+    /// index in `triangles`, `hull` and `hidden`, so that nearby points get
+    /// nearby indices. Triangles are then sorted by their lowest corner,
+    /// keeping the corners of each triangle in the same rotation. The
+    /// triangulation stays the same. Returns the previous index of every
+    /// point, which maps per-point data to the new numbering.
+    #[cfg_attr(feature = "wasm", wasm_bindgen(js_name = "renumber"))]
+    pub fn renumber(&mut self) -> Vec<u32> {
+        let order = hilbert_order(&self.coords);
+        let mut new_ids = vec![0; order.len()];
+        for (i, &old) in order.iter().enumerate() {
+            new_ids[old as usize] = i as u32;
+        }
+
+        self.coords = order
+            .iter()
+            .flat_map(|&i| [self.coords[2 * i as usize], self.coords[2 * i as usize + 1]])
+            .collect();
+        if !self.weights.is_empty() {
+            self.weights = order.iter().map(|&i| self.weights[i as usize]).collect();
+        }
+        for i in self
+            .triangles
+            .iter_mut()
+            .chain(&mut self.hull)
+            .chain(&mut self.hidden)
+        {
+            *i = new_ids[*i as usize];
+        }
+        self.hidden.sort_unstable();
+
+        let mut triangle_order: Vec<usize> = (0..self.triangles.len() / 3).collect();
+        triangle_order.sort_by_key(|&t| self.triangles[3 * t..3 * t + 3].iter().min().copied());
+        let mut new_triangles = vec![0; triangle_order.len()];
+        for (t, &old) in triangle_order.iter().enumerate() {
+            new_triangles[old] = t;
+        }
+
+        let mut triangles = Vec::with_capacity(self.triangles.len());
+        let mut halfedges = Vec::with_capacity(self.halfedges.len());
+        for &t in &triangle_order {
+            for e in 3 * t..3 * t + 3 {
+                triangles.push(self.triangles[e]);
+                let twin = self.halfedges[e];
+                halfedges.push(if twin < 0 {
+                    -1
+                } else {
+                    let twin = twin as usize;
+                    (3 * new_triangles[twin / 3] + twin % 3) as i32
+                });
+            }
+        }
+        self.triangles = triangles;
+        self.halfedges = halfedges;
+
+        order
+    }
+}
+
+// Orders point indices in biased randomized rounds, each along a Hilbert
+// curve. Every point lands in the last round with probability 1/2, in the
+// one before with probability 1/4, and so on, drawn from a hash of its
+// index so that the order is the same on every run.
+pub(crate) fn brio_order(coords: &[f64]) -> Vec<u32> {
+    let mut rank = vec![0; coords.len() / 2];
+    for (r, i) in hilbert_order(coords).into_iter().enumerate() {
+        rank[i as usize] = r;
+    }
+
+    let mut order: Vec<u32> = (0..rank.len() as u32).collect();
+    order.sort_unstable_by_key(|&i| (core::cmp::Reverse(round(i)), rank[i as usize]));
+    order
+}
+
+// Round of point i, counted back from the last one
+fn round(i: u32) -> u32 {
+    // SplitMix64 finalizer
+    let mut z = (i as u64).wrapping_add(0x9e3779b97f4a7c15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+    (z ^ (z >> 31)).trailing_zeros()
+}
+
+// Sorts point indices along a Hilbert curve over the bounding box, so that
+// consecutive points are close to each other
+pub(crate) fn hilbert_order(coords: &[f64]) -> Vec<u32> {
+    let mut min_x = f64::INFINITY;
+    let mut min_y = f64::INFINITY;
+    let mut max_x = f64::NEG_INFINITY;
+    let mut max_y = f64::NEG_INFINITY;
+    for p in coords.chunks_exact(2) {
+        min_x = min_x.min(p[0]);
+        min_y = min_y.min(p[1]);
+        max_x = max_x.max(p[0]);
+        max_y = max_y.max(p[1]);
+    }
+
+    let side = (1u32 << 16) as f64 - 1.0;
+    let scale_x = if max_x > min_x {
+        side / (max_x - min_x)
+    } else {
+        0.0
+    };
+    let scale_y = if max_y > min_y {
+        side / (max_y - min_y)
+    } else {
+        0.0
+    };
+
+    let mut keyed: Vec<(u32, u32)> = coords
+        .chunks_exact(2)
+        .enumerate()
+        .map(|(i, p)| {
+            let x = ((p[0] - min_x) * scale_x) as u32;
+            let y = ((p[1] - min_y) * scale_y) as u32;
+            (hilbert_index(x, y), i as u32)
+        })
+        .collect();
+    keyed.sort_unstable();
+    keyed.into_iter().map(|(_, i)| i).collect()
+}
+
+// Position of the cell (x, y) along the Hilbert curve filling a 2^16 by
+// 2^16 grid
+fn hilbert_index(mut x: u32, mut y: u32) -> u32 {
+    let mut index = 0;
+    let mut s = 1 << 15;
+    while s > 0 {
+        let rx = u32::from(x & s > 0);
+        let ry = u32::from(y & s > 0);
+        index += s * s * ((3 * rx) ^ ry);
+
+        // Rotate the quadrant so that the curve inside it starts and ends
+        // next to its neighbors
+        if ry == 0 {
+            if rx == 1 {
+                x = 0xffff - x;
+                y = 0xffff - y;
+            }
+            core::mem::swap(&mut x, &mut y);
+        }
+        s >>= 1;
+    }
+    index
+}
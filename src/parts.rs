@@ -0,0 +1,124 @@
+//! Triangulations rebuilt from their arrays
+//!
+//! [`Delaunator::from_parts`] takes back the arrays of a triangulation that
+//! was cached or sent elsewhere, and checks them with
+//! [`Delaunator::validate`] instead of triangulating the points again.
+//!
+//! With the `serde` feature, a `Delaunator` serializes as its coordinates
+//! and output arrays, plus the weights and hidden points of a weighted
+//! triangulation, and deserializes through the same checks.
+
+use alloc::vec::Vec;
+
+#[cfg(feature = "serde")]
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::{check_coords, index, Delaunator, Error};
+
+impl Delaunator {
+    /// Rebuilds a triangulation from its coordinates and the arrays of its
+    /// `triangles`, `halfedges` and `hull`
+    ///
+    /// Runs in linear time, without triangulating the points again. Returns
+    /// error if the coordinates are invalid, or if the arrays fail any check
+    /// of `validate`. Later calls to `update` use the default options.
+    pub fn from_parts(
+        coords: Vec<f64>,
+        triangles: Vec<u32>,
+        halfedges: Vec<i32>,
+        hull: Vec<u32>,
+    ) -> Result<Delaunator, Error> {
+        Delaunator::from_arrays(coords, Vec::new(), triangles, halfedges, hull, Vec::new())
+    }
+
+    // Same as `from_parts` for a weighted triangulation when `weights` is
+    // not empty
+    pub(crate) fn from_arrays(
+        coords: Vec<f64>,
+        weights: Vec<f64>,
+        triangles: Vec<u32>,
+        halfedges: Vec<i32>,
+        hull: Vec<u32>,
+        hidden: Vec<u32>,
+    ) -> Result<Delaunator, Error> {
+        check_coords(&coords)?;
+        let n = coords.len() / 2;
+        if !index::fits(n, i32::MAX as u128) {
+            return Err(Error::TooManyPoints);
+        }
+
+        if !weights.is_empty() && (weights.len() != n || weights.iter().any(|w| !w.is_finite())) {
+            return Err(Error::InvalidWeights);
+        }
+
+        let mut delaunator = Delaunator::allocate(coords, weights);
+        delaunator.triangles = triangles;
+        delaunator.halfedges = halfedges;
+        delaunator.hull = hull;
+        delaunator.hidden = hidden;
+
+        if !delaunator.validate().is_valid() || delaunator.hidden.iter().any(|&i| i as usize >= n) {
+            return Err(Error::InvalidTriangulation);
+        }
+        Ok(delaunator)
+    }
+}
+
+// Serialized form of a triangulation, leaving out the empty weights and
+// hidden points of an unweighted one
+#[cfg(feature = "serde")]
+#[derive(Serialize)]
+struct PartsRef<'a> {
+    coords: &'a [f64],
+    #[serde(skip_serializing_if = "<[f64]>::is_empty")]
+    weights: &'a [f64],
+    triangles: &'a [u32],
+    halfedges: &'a [i32],
+    hull: &'a [u32],
+    #[serde(skip_serializing_if = "<[u32]>::is_empty")]
+    hidden: &'a [u32],
+}
+
+#[cfg(feature = "serde")]
+#[derive(Deserialize)]
+struct Parts {
+    coords: Vec<f64>,
+    #[serde(default)]
+    weights: Vec<f64>,
+    triangles: Vec<u32>,
+    halfedges: Vec<i32>,
+    hull: Vec<u32>,
+    #[serde(default)]
+    hidden: Vec<u32>,
+}
+
+#[cfg(feature = "serde")]
+impl Serialize for Delaunator {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        PartsRef {
+            coords: &self.coords,
+            weights: &self.weights,
+            triangles: &self.triangles,
+            halfedges: &self.halfedges,
+            hull: &self.hull,
+            hidden: &self.hidden,
+        }
+        .serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for Delaunator {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Delaunator, D::Error> {
+        let parts = Parts::deserialize(deserializer)?;
+        Delaunator::from_arrays(
+            parts.coords,
+            parts.weights,
+            parts.triangles,
+            parts.halfedges,
+            parts.hull,
+            parts.hidden,
+        )
+        .map_err(de::Error::custom)
+    }
+}
@@ -0,0 +1,240 @@
+//! Periodic Delaunay triangulation on a flat torus
+//!
+//! Points live in the rectangle `[0, width) x [0, height)` whose opposite
+//! sides are glued together, as for tileable textures or periodic boundary
+//! conditions. The triangulation is computed by surrounding the points with
+//! their 8 translated copies, triangulating the 9 tiles with [`Delaunator`]
+//! and keeping exactly one copy of every triangle.
+//!
+//! A triangle may cross the sides of the rectangle, so each of its corners
+//! records the tile its point was taken from as an offset in `{-1, 0, 1}`
+//! along each axis. Since the torus has no boundary, there is no hull and
+//! every halfedge has a twin.
+
+use alloc::{collections::BTreeMap, vec, vec::Vec};
+
+#[cfg(feature = "wasm")]
+use wasm_bindgen::prelude::*;
+
+use crate::{next_halfedge, Delaunator, Error};
+
+/// Delaunay triangulation of points on a flat torus
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+#[derive(Debug)]
+pub struct PeriodicDelaunay {
+    // Input coordinates wrapped into the domain [x0, y0, x1, y1, ...]
+    pub(crate) coords: Vec<f64>,
+    width: f64,
+    height: f64,
+
+    #[cfg_attr(feature = "wasm", wasm_bindgen(skip))]
+    pub triangles: Vec<u32>,
+    #[cfg_attr(feature = "wasm", wasm_bindgen(skip))]
+    pub halfedges: Vec<i32>,
+    /// Tile offset of every triangle corner, `[dx, dy]` for each entry of
+    /// `triangles`: corner `e` lies at
+    /// `(x + offsets[2 * e] * width, y + offsets[2 * e + 1] * height)`
+    #[cfg_attr(feature = "wasm", wasm_bindgen(skip))]
+    pub offsets: Vec<i8>,
+}
+
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+impl PeriodicDelaunay {
+    /// Triangulates points on a torus of the given width and height
+    ///
+    /// The input format is `[x0, y0, x1, y1, ...]`; points outside the
+    /// domain are wrapped into it. Returns error if the input is invalid or
+    /// if the points are too sparse for the triangulation to be well defined
+    /// on the torus.
+    #[cfg_attr(feature = "wasm", wasm_bindgen(constructor))]
+    pub fn new(coords: Vec<f64>, width: f64, height: f64) -> Result<PeriodicDelaunay, Error> {
+        if coords.is_empty() || !coords.len().is_multiple_of(2) {
+            return Err(Error::InvalidCoordinates);
+        }
+
+        if coords.iter().any(|v| !v.is_finite()) {
+            return Err(Error::NonFiniteCoordinate);
+        }
+
+        if !(width > 0.0 && width.is_finite() && height > 0.0 && height.is_finite()) {
+            return Err(Error::InvalidDomain);
+        }
+
+        let coords: Vec<f64> = coords
+            .chunks_exact(2)
+            .flat_map(|p| [wrap(p[0], width), wrap(p[1], height)])
+            .collect();
+
+        let mut periodic = PeriodicDelaunay {
+            coords,
+            width,
+            height,
+            triangles: Vec::new(),
+            halfedges: Vec::new(),
+            offsets: Vec::new(),
+        };
+        periodic.triangulate()?;
+
+        Ok(periodic)
+    }
+}
+
+// JavaScript API methods for web use
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+impl PeriodicDelaunay {
+    /// Get triangulation result as array of indices
+    ///
+    /// Returns a Uint32Array containing indices that form triangles
+    /// (each group of 3 values represents a triangle)
+    #[wasm_bindgen(getter, js_name = "triangles")]
+    pub fn get_triangles(&self) -> js_sys::Uint32Array {
+        let array = js_sys::Uint32Array::new_with_length(self.triangles.len() as u32);
+        array.copy_from(&self.triangles);
+        array
+    }
+
+    /// Get halfedges result as array
+    ///
+    /// Returns an Int32Array containing halfedge indices
+    /// that allow traversal of the triangulation
+    #[wasm_bindgen(getter, js_name = "halfedges")]
+    pub fn get_halfedges(&self) -> js_sys::Int32Array {
+        let array = js_sys::Int32Array::new_with_length(self.halfedges.len() as u32);
+        array.copy_from(&self.halfedges);
+        array
+    }
+
+    /// Get corner offsets as array
+    ///
+    /// Returns an Int8Array containing the tile offset [dx, dy]
+    /// of the point used by each triangle corner
+    #[wasm_bindgen(getter, js_name = "offsets")]
+    pub fn get_offsets(&self) -> js_sys::Int8Array {
+        let array = js_sys::Int8Array::new_with_length(self.offsets.len() as u32);
+        array.copy_from(&self.offsets);
+        array
+    }
+
+    /// Get wrapped coordinates as array
+    ///
+    /// Returns a Float64Array containing the input coordinates
+    /// wrapped into the domain, in the format [x0, y0, x1, y1, ...]
+    #[wasm_bindgen(getter, js_name = "coords")]
+    pub fn get_coords(&self) -> js_sys::Float64Array {
+        let array = js_sys::Float64Array::new_with_length(self.coords.len() as u32);
+        array.copy_from(&self.coords);
+        array
+    }
+}
+
+impl PeriodicDelaunay {
+    /// Returns the position of triangle corner `e`, shifted by its offset
+    pub fn corner(&self, e: usize) -> (f64, f64) {
+        let i = self.triangles[e] as usize;
+        (
+            self.coords[2 * i] + self.offsets[2 * e] as f64 * self.width,
+            self.coords[2 * i + 1] + self.offsets[2 * e + 1] as f64 * self.height,
+        )
+    }
+
+    fn triangulate(&mut self) -> Result<(), Error> {
+        let n = self.coords.len() / 2;
+
+        // Tile k holds the copy of every point shifted by TILES[k], so copy
+        // k * n + i is point i in tile k
+        let mut copies = Vec::with_capacity(18 * n);
+        for (dx, dy) in TILES {
+            for p in self.coords.chunks_exact(2) {
+                copies.push(p[0] + dx as f64 * self.width);
+                copies.push(p[1] + dy as f64 * self.height);
+            }
+        }
+
+        let tiled = Delaunator::new(copies)?;
+        let corner = |c: u32| {
+            let (dx, dy) = TILES[c as usize / n];
+            (c % n as u32, dx, dy)
+        };
+
+        let mut triangles = Vec::new();
+        let mut offsets = Vec::new();
+
+        for t in tiled.triangles.chunks_exact(3) {
+            let corners = [corner(t[0]), corner(t[1]), corner(t[2])];
+
+            // Every triangle of the torus appears once per tile it can be
+            // shifted to. Keep the copy whose lowest corner, comparing point
+            // index and then offset, lies in the central tile.
+            let anchor = corners.iter().min().unwrap();
+            if anchor.1 != 0 || anchor.2 != 0 {
+                continue;
+            }
+
+            for (i, dx, dy) in corners {
+                triangles.push(i);
+                offsets.push(dx);
+                offsets.push(dy);
+            }
+        }
+
+        // Pair halfedges, the twin of the edge from (a, oa) to (b, ob) goes
+        // from b to a with the same offset difference reversed. The 9 tiles
+        // don't triangulate into a consistent torus when some empty circle
+        // is as wide as the domain, leaving edges repeated or unpaired.
+        let mut edges: BTreeMap<(u32, u32, i8, i8), usize> = BTreeMap::new();
+        for e in 0..triangles.len() {
+            let f = next_halfedge(e);
+            let dx = offsets[2 * f] - offsets[2 * e];
+            let dy = offsets[2 * f + 1] - offsets[2 * e + 1];
+            if edges
+                .insert((triangles[e], triangles[f], dx, dy), e)
+                .is_some()
+            {
+                return Err(Error::TooSparse);
+            }
+        }
+
+        let mut halfedges = vec![-1; triangles.len()];
+        for e in 0..triangles.len() {
+            let f = next_halfedge(e);
+            let dx = offsets[2 * e] - offsets[2 * f];
+            let dy = offsets[2 * e + 1] - offsets[2 * f + 1];
+            match edges.get(&(triangles[f], triangles[e], dx, dy)) {
+                Some(&twin) => halfedges[e] = twin as i32,
+                None => return Err(Error::TooSparse),
+            }
+        }
+
+        self.triangles = triangles;
+        self.halfedges = halfedges;
+        self.offsets = offsets;
+        Ok(())
+    }
+}
+
+// Wraps a coordinate into [0, size)
+fn wrap(v: f64, size: f64) -> f64 {
+    // Same as rem_euclid, which is not in core, and can round tiny
+    // negative values up to size itself
+    let v = v % size;
+    let v = if v < 0.0 { v + size } else { v };
+    if v < size {
+        v
+    } else {
+        0.0
+    }
+}
+
+// Tile offsets, the central tile first
+const TILES: [(i8, i8); 9] = [
+    (0, 0),
+    (-1, -1),
+    (0, -1),
+    (1, -1),
+    (-1, 0),
+    (1, 0),
+    (-1, 1),
+    (0, 1),
+    (1, 1),
+];
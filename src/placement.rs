@@ -0,0 +1,351 @@
+//! Placement queries: largest empty circle and pole of inaccessibility
+//!
+//! Both look for the point of a region that lies farthest from something:
+//! from the input points for the largest empty circle, and from the region
+//! boundary itself for the pole of inaccessibility. Regions are polygons
+//! given as rings `[x0, y0, x1, y1, ...]` without repeating the first point;
+//! the first ring is the outer boundary and the others are holes, so the
+//! rings of a [`Band`](crate::contour::Band) can be used directly.
+
+use alloc::{collections::BinaryHeap, vec::Vec};
+use core::cmp::Ordering;
+
+use num_traits::Float;
+
+use crate::calipers::Circle;
+use crate::{circumcenter, dist, Delaunator};
+
+impl Delaunator {
+    /// Finds the largest circle containing no input point whose center lies
+    /// within a region
+    ///
+    /// The region is the convex hull of the points when `polygon` is `None`.
+    /// The best center is either a vertex of the Voronoi diagram (a triangle
+    /// circumcenter), a point where a Voronoi edge crosses the region
+    /// boundary, or a vertex of the region, so only these are tried. The
+    /// boundary crossings are found by walking each boundary edge through
+    /// the Voronoi cells it traverses. Returns `None` when there are no
+    /// triangles.
+    pub fn largest_empty_circle(&self, polygon: Option<&[Vec<f64>]>) -> Option<Circle> {
+        if self.triangles.is_empty() {
+            return None;
+        }
+
+        let hull_ring;
+        let rings = match polygon {
+            Some(rings) => rings,
+            None => {
+                hull_ring = [self
+                    .hull
+                    .iter()
+                    .flat_map(|&i| [self.coords[2 * i as usize], self.coords[2 * i as usize + 1]])
+                    .collect::<Vec<f64>>()];
+                &hull_ring[..]
+            }
+        };
+
+        // Best center so far with its squared distance to the nearest point
+        let mut best: Option<(f64, f64, f64)> = None;
+        let mut consider = |x: f64, y: f64, d: f64| {
+            if best.is_none_or(|b| d > b.2) {
+                best = Some((x, y, d));
+            }
+        };
+
+        // Voronoi vertices inside the region
+        for t in self.triangles.chunks_exact(3) {
+            let [a, b, c] = [t[0], t[1], t[2]].map(|i| i as usize);
+            let (x, y) = circumcenter(
+                self.coords[2 * a],
+                self.coords[2 * a + 1],
+                self.coords[2 * b],
+                self.coords[2 * b + 1],
+                self.coords[2 * c],
+                self.coords[2 * c + 1],
+            );
+            if x.is_finite() && y.is_finite() && contains(rings, x, y) {
+                consider(x, y, dist(x, y, self.coords[2 * a], self.coords[2 * a + 1]));
+            }
+        }
+
+        // Region vertices and Voronoi edge crossings along the boundary
+        let inedges = self.inedges();
+        let mut neighbors = Vec::new();
+        let mut site = self.hull[0] as usize;
+        for ring in rings {
+            let n = ring.len() / 2;
+            for k in 0..n {
+                let (px, py) = (ring[2 * k], ring[2 * k + 1]);
+                let (qx, qy) = (ring[2 * ((k + 1) % n)], ring[2 * ((k + 1) % n) + 1]);
+
+                site = self.nearest_point(&inedges, &mut neighbors, px, py, site);
+                let (sx, sy) = (self.coords[2 * site], self.coords[2 * site + 1]);
+                consider(px, py, dist(px, py, sx, sy));
+
+                // Within the cell of the current site, the edge leaves
+                // through the bisector with the neighbor it reaches first
+                let (dx, dy) = (qx - px, qy - py);
+                let mut s = site;
+                let mut along = 0.0;
+                for _ in 0..self.coords.len() / 2 {
+                    let (sx, sy) = (self.coords[2 * s], self.coords[2 * s + 1]);
+                    self.neighbors(&inedges, s, &mut neighbors);
+
+                    let mut exit = None;
+                    for &t in &neighbors {
+                        let (tx, ty) =
+                            (self.coords[2 * t as usize], self.coords[2 * t as usize + 1]);
+                        let towards = dx * (tx - sx) + dy * (ty - sy);
+                        if towards <= 0.0 {
+                            continue;
+                        }
+                        let offset = tx * tx + ty * ty - sx * sx - sy * sy;
+                        let lambda =
+                            (offset - 2.0 * (px * (tx - sx) + py * (ty - sy))) / (2.0 * towards);
+                        if lambda > along && exit.is_none_or(|(l, _)| lambda < l) {
+                            exit = Some((lambda, t as usize));
+                        }
+                    }
+
+                    let Some((lambda, t)) = exit.filter(|&(l, _)| l < 1.0) else {
+                        break;
+                    };
+                    let (x, y) = (px + lambda * dx, py + lambda * dy);
+                    consider(x, y, dist(x, y, sx, sy));
+                    s = t;
+                    along = lambda;
+                }
+            }
+        }
+
+        best.map(|(x, y, d)| Circle {
+            x,
+            y,
+            r: Float::sqrt(d),
+        })
+    }
+
+    // Finds the input point closest to (x, y) by walking the triangulation
+    // greedily from point `start`, which always ends at the nearest point
+    fn nearest_point(
+        &self,
+        inedges: &[i32],
+        neighbors: &mut Vec<u32>,
+        x: f64,
+        y: f64,
+        start: usize,
+    ) -> usize {
+        let mut current = start;
+        let mut d = dist(x, y, self.coords[2 * current], self.coords[2 * current + 1]);
+        loop {
+            self.neighbors(inedges, current, neighbors);
+            let mut next = current;
+            for &i in neighbors.iter() {
+                let i = i as usize;
+                let di = dist(x, y, self.coords[2 * i], self.coords[2 * i + 1]);
+                if di < d {
+                    d = di;
+                    next = i;
+                }
+            }
+            if next == current {
+                return current;
+            }
+            current = next;
+        }
+    }
+}
+
+/// Finds the pole of inaccessibility of a polygon
+///
+/// This is the interior point farthest from the polygon boundary, the best
+/// place for a label, returned with its distance to the boundary as the
+/// radius. Implements the polylabel algorithm: the bounding box is split
+/// into square cells, and cells are refined in order of the best distance
+/// they could still contain until none can improve on the best point found
+/// by more than `precision`. Precisions finer than a billionth of the
+/// polygon size, including zero, negative and NaN precisions, are raised to
+/// that, since the search would not end otherwise. Returns `None` if the
+/// outer ring has fewer than three points.
+pub fn pole_of_inaccessibility(rings: &[Vec<f64>], precision: f64) -> Option<Circle> {
+    let outer = rings.first().filter(|r| r.len() >= 6)?;
+
+    let mut min_x = f64::INFINITY;
+    let mut min_y = f64::INFINITY;
+    let mut max_x = f64::NEG_INFINITY;
+    let mut max_y = f64::NEG_INFINITY;
+    for p in outer.chunks_exact(2) {
+        min_x = min_x.min(p[0]);
+        min_y = min_y.min(p[1]);
+        max_x = max_x.max(p[0]);
+        max_y = max_y.max(p[1]);
+    }
+
+    let cell_size = (max_x - min_x).min(max_y - min_y);
+    if cell_size == 0.0 {
+        return Some(Circle {
+            x: min_x,
+            y: min_y,
+            r: 0.0,
+        });
+    }
+
+    // f64::max returns the other operand when one is NaN
+    let precision = precision.max(1e-9 * (max_x - min_x).max(max_y - min_y));
+
+    let cell = |x: f64, y: f64, half: f64| {
+        let d = signed_distance(rings, x, y);
+        Cell {
+            x,
+            y,
+            half,
+            d,
+            max: d + half * core::f64::consts::SQRT_2,
+        }
+    };
+
+    // Seed the search with the centroid and the center of the bounding box
+    let (cx, cy) = ring_centroid(outer);
+    let mut best = cell(cx, cy, 0.0);
+    let center = cell((min_x + max_x) / 2.0, (min_y + max_y) / 2.0, 0.0);
+    if center.d > best.d {
+        best = center;
+    }
+
+    let mut queue = BinaryHeap::new();
+    let half = cell_size / 2.0;
+    let mut x = min_x;
+    while x < max_x {
+        let mut y = min_y;
+        while y < max_y {
+            queue.push(cell(x + half, y + half, half));
+            y += cell_size;
+        }
+        x += cell_size;
+    }
+
+    while let Some(c) = queue.pop() {
+        if c.d > best.d {
+            best = c;
+        }
+        if c.max - best.d <= precision {
+            continue;
+        }
+
+        let half = c.half / 2.0;
+        for (sx, sy) in [(-1.0, -1.0), (1.0, -1.0), (-1.0, 1.0), (1.0, 1.0)] {
+            queue.push(cell(c.x + sx * half, c.y + sy * half, half));
+        }
+    }
+
+    Some(Circle {
+        x: best.x,
+        y: best.y,
+        r: best.d.max(0.0),
+    })
+}
+
+// A square cell of the pole search, ordered by the best distance it could
+// contain
+#[derive(Clone, Copy)]
+struct Cell {
+    x: f64,
+    y: f64,
+    half: f64,
+    // Signed distance from the center to the polygon boundary
+    d: f64,
+    max: f64,
+}
+
+impl PartialEq for Cell {
+    fn eq(&self, other: &Self) -> bool {
+        self.max.total_cmp(&other.max) == Ordering::Equal
+    }
+}
+
+impl Eq for Cell {}
+
+impl PartialOrd for Cell {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Cell {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.max.total_cmp(&other.max)
+    }
+}
+
+// Even-odd test over all rings, so that holes are excluded
+fn contains(rings: &[Vec<f64>], x: f64, y: f64) -> bool {
+    let mut inside = false;
+    for ring in rings {
+        let n = ring.len() / 2;
+        for k in 0..n {
+            let (ax, ay) = (ring[2 * k], ring[2 * k + 1]);
+            let (bx, by) = (ring[2 * ((k + 1) % n)], ring[2 * ((k + 1) % n) + 1]);
+            if (ay > y) != (by > y) && x < (bx - ax) * (y - ay) / (by - ay) + ax {
+                inside = !inside;
+            }
+        }
+    }
+    inside
+}
+
+// Distance from (x, y) to the polygon boundary, negative outside
+fn signed_distance(rings: &[Vec<f64>], x: f64, y: f64) -> f64 {
+    let mut min = f64::INFINITY;
+    for ring in rings {
+        let n = ring.len() / 2;
+        for k in 0..n {
+            let (ax, ay) = (ring[2 * k], ring[2 * k + 1]);
+            let (bx, by) = (ring[2 * ((k + 1) % n)], ring[2 * ((k + 1) % n) + 1]);
+            min = min.min(segment_distance(x, y, ax, ay, bx, by));
+        }
+    }
+
+    let d = Float::sqrt(min);
+    if contains(rings, x, y) {
+        d
+    } else {
+        -d
+    }
+}
+
+// Squared distance from (x, y) to the segment from a to b
+fn segment_distance(x: f64, y: f64, ax: f64, ay: f64, bx: f64, by: f64) -> f64 {
+    let (dx, dy) = (bx - ax, by - ay);
+    let length = dx * dx + dy * dy;
+    let t = if length > 0.0 {
+        (((x - ax) * dx + (y - ay) * dy) / length).clamp(0.0, 1.0)
+    } else {
+        0.0
+    };
+    dist(x, y, ax + t * dx, ay + t * dy)
+}
+
+// Area centroid of a ring, or its first point when it encloses no area.
+// Sums are taken relative to the first point, so that a large offset does
+// not round the area away.
+fn ring_centroid(ring: &[f64]) -> (f64, f64) {
+    let n = ring.len() / 2;
+    let (ox, oy) = (ring[0], ring[1]);
+    let (mut cx, mut cy, mut area) = (0.0, 0.0, 0.0);
+    for k in 0..n {
+        let (ax, ay) = (ring[2 * k] - ox, ring[2 * k + 1] - oy);
+        let (bx, by) = (
+            ring[2 * ((k + 1) % n)] - ox,
+            ring[2 * ((k + 1) % n) + 1] - oy,
+        );
+        let cross = ax * by - bx * ay;
+        cx += (ax + bx) * cross;
+        cy += (ay + by) * cross;
+        area += cross * 3.0;
+    }
+    if area == 0.0 {
+        (ox, oy)
+    } else {
+        (ox + cx / area, oy + cy / area)
+    }
+}
@@ -0,0 +1,184 @@
+//! Exact power test for weighted points
+//!
+//! The regular triangulation decides whether a weighted point conflicts with
+//! a triangle from the side of the plane through the lifted corners
+//! `(x, y, x² + y² - w)` it lies on. Lifting rounds, so the test is first
+//! evaluated in floating point with an error bound, as the predicates of
+//! `robust` are, and recomputed exactly from the input coordinates and
+//! weights with floating point expansions when the bound cannot settle the
+//! sign. Both lift the points relative to the tested point, so that only
+//! the spread of the points, not their distance to the origin, needs to fit
+//! in a double when squared.
+
+use alloc::vec::Vec;
+
+// Machine epsilon as used by Shewchuk, half of f64::EPSILON
+const EPSILON: f64 = f64::EPSILON / 2.0;
+
+// Error bound of the floating point determinant relative to its permanent.
+// Shewchuk bounds the error of the in-circle determinant by (10 + 96ε)ε
+// times its permanent, where the lifted column holds dd = dx² + dy². Here
+// each lifted entry takes two more roundings to subtract its weight and add
+// the weight of p, each off by at most ε times dd + |w| + |wp|, which the
+// permanent below uses in place of dd. To first order the bound is then
+// 12ε, and (16 + 256ε)ε also covers the second order terms.
+const ERROR_BOUND: f64 = (16.0 + 256.0 * EPSILON) * EPSILON;
+
+// Splits a double in two halves of 26 bits for exact products
+const SPLITTER: f64 = 134_217_729.0;
+
+/// Returns a value with the sign of the determinant
+///
+/// ```text
+/// | ax - px  ay - py  La - Lp |
+/// | bx - px  by - py  Lb - Lp |
+/// | cx - px  cy - py  Lc - Lp |
+/// ```
+///
+/// where `Lq = qx² + qy² - wq` lifts point `q` of weight `wq` (the last
+/// weight is the one of `p`). The determinant is negative when the lifted
+/// `p` lies below the plane through the lifted `a`, `b` and `c`, which
+/// wind clockwise with the y axis pointing up, and zero when the four lie
+/// on the same plane. Without weights this is the in-circle test.
+pub(crate) fn power(
+    a: (f64, f64),
+    b: (f64, f64),
+    c: (f64, f64),
+    p: (f64, f64),
+    w: [f64; 4],
+) -> f64 {
+    let dx = a.0 - p.0;
+    let dy = a.1 - p.1;
+    let ex = b.0 - p.0;
+    let ey = b.1 - p.1;
+    let fx = c.0 - p.0;
+    let fy = c.1 - p.1;
+
+    // Lifting relative to p changes the last column by a combination of
+    // the first two, which keeps the determinant
+    let dd = dx * dx + dy * dy;
+    let ee = ex * ex + ey * ey;
+    let ff = fx * fx + fy * fy;
+    let ap = dd - w[0] + w[3];
+    let bp = ee - w[1] + w[3];
+    let cp = ff - w[2] + w[3];
+
+    let eyfx = ey * fx;
+    let exfy = ex * fy;
+    let dyfx = dy * fx;
+    let dxfy = dx * fy;
+    let dyex = dy * ex;
+    let dxey = dx * ey;
+
+    let det = ap * (exfy - eyfx) - bp * (dxfy - dyfx) + cp * (dxey - dyex);
+
+    let wp = w[3].abs();
+    let permanent = (exfy.abs() + eyfx.abs()) * (dd + w[0].abs() + wp)
+        + (dxfy.abs() + dyfx.abs()) * (ee + w[1].abs() + wp)
+        + (dxey.abs() + dyex.abs()) * (ff + w[2].abs() + wp);
+    let bound = ERROR_BOUND * permanent;
+    if det > bound || -det > bound {
+        return det;
+    }
+
+    exact(a, b, c, p, w)
+}
+
+// Evaluates the same determinant as the filter, with the coordinate
+// differences kept exactly as two-component expansions and every product
+// and sum computed exactly
+fn exact(a: (f64, f64), b: (f64, f64), c: (f64, f64), p: (f64, f64), w: [f64; 4]) -> f64 {
+    let [d, e, f] = [a, b, c].map(|q| (difference(q.0, p.0), difference(q.1, p.1)));
+    let lift = |(x, y): &(Vec<f64>, Vec<f64>), weight: f64| {
+        let mut lifted = product(x, x);
+        for &v in &product(y, y) {
+            grow(&mut lifted, v);
+        }
+        grow(&mut lifted, -weight);
+        grow(&mut lifted, w[3]);
+        lifted
+    };
+    // u.x v.y - u.y v.x
+    let minor = |u: &(Vec<f64>, Vec<f64>), v: &(Vec<f64>, Vec<f64>)| {
+        let mut minor = product(&u.0, &v.1);
+        for &x in &product(&u.1, &v.0) {
+            grow(&mut minor, -x);
+        }
+        minor
+    };
+
+    let mut det = Vec::new();
+    for (lifted, minor, sign) in [
+        (lift(&d, w[0]), minor(&e, &f), 1.0),
+        (lift(&e, w[1]), minor(&d, &f), -1.0),
+        (lift(&f, w[2]), minor(&d, &e), 1.0),
+    ] {
+        for &x in &product(&lifted, &minor) {
+            grow(&mut det, sign * x);
+        }
+    }
+
+    // Components grow in magnitude, so the last one carries the sign
+    det.last().copied().unwrap_or(0.0)
+}
+
+// a - b as an expansion
+fn difference(a: f64, b: f64) -> Vec<f64> {
+    let (sum, error) = two_sum(a, -b);
+    let mut e = Vec::new();
+    grow(&mut e, error);
+    grow(&mut e, sum);
+    e
+}
+
+// Product of two expansions as an expansion
+fn product(e: &[f64], f: &[f64]) -> Vec<f64> {
+    let mut result = Vec::new();
+    for &x in e {
+        for &y in f {
+            let (high, low) = two_product(x, y);
+            grow(&mut result, low);
+            grow(&mut result, high);
+        }
+    }
+    result
+}
+
+// Adds b to the nonoverlapping expansion e, dropping zero components
+fn grow(e: &mut Vec<f64>, b: f64) {
+    let mut q = b;
+    let mut k = 0;
+    for i in 0..e.len() {
+        let (sum, error) = two_sum(q, e[i]);
+        q = sum;
+        if error != 0.0 {
+            e[k] = error;
+            k += 1;
+        }
+    }
+    e.truncate(k);
+    if q != 0.0 {
+        e.push(q);
+    }
+}
+
+fn two_sum(a: f64, b: f64) -> (f64, f64) {
+    let x = a + b;
+    let b_virtual = x - a;
+    let a_virtual = x - b_virtual;
+    (x, (a - a_virtual) + (b - b_virtual))
+}
+
+fn two_product(a: f64, b: f64) -> (f64, f64) {
+    let x = a * b;
+    let (a_high, a_low) = split(a);
+    let (b_high, b_low) = split(b);
+    let error = x - a_high * b_high - a_low * b_high - a_high * b_low;
+    (x, a_low * b_low - error)
+}
+
+fn split(a: f64) -> (f64, f64) {
+    let c = SPLITTER * a;
+    let high = c - (c - a);
+    (high, a - high)
+}
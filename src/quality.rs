@@ -0,0 +1,202 @@
+//! Mesh quality metrics
+//!
+//! Per-triangle shape measures commonly used to judge meshes before finite
+//! element analysis, and histograms summarizing them over the whole
+//! triangulation. Angles are in degrees. Both ratios equal their lowest
+//! value for an equilateral triangle and grow without bound as a triangle
+//! flattens, reaching infinity for degenerate ones.
+
+use alloc::{vec, vec::Vec};
+
+use num_traits::Float;
+#[cfg(feature = "wasm")]
+use wasm_bindgen::prelude::*;
+
+use crate::{orient2d, Delaunator};
+
+/// Shape measures of a single triangle
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TriangleQuality {
+    /// Smallest interior angle in degrees
+    pub min_angle: f64,
+    /// Largest interior angle in degrees
+    pub max_angle: f64,
+    /// Circumradius divided by twice the inradius, 1 for an equilateral
+    /// triangle
+    pub aspect_ratio: f64,
+    pub area: f64,
+    /// Circumradius divided by the shortest edge, `1 / sqrt(3)` for an
+    /// equilateral triangle
+    pub radius_edge_ratio: f64,
+}
+
+/// Counts of values falling into equal-width bins over `[start, end]`
+///
+/// Values outside of the range are counted in the first or last bin.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Histogram {
+    pub start: f64,
+    pub end: f64,
+    pub counts: Vec<usize>,
+}
+
+/// Aggregate of one metric over all triangles
+#[derive(Debug, Clone, PartialEq)]
+pub struct MetricSummary {
+    pub min: f64,
+    pub max: f64,
+    pub mean: f64,
+    pub histogram: Histogram,
+}
+
+/// Aggregates of every metric over all triangles
+#[derive(Debug, Clone, PartialEq)]
+pub struct QualityReport {
+    /// Histogram over `[0, 180]` degrees
+    pub min_angle: MetricSummary,
+    /// Histogram over `[0, 180]` degrees
+    pub max_angle: MetricSummary,
+    /// Histogram over the range of finite values
+    pub aspect_ratio: MetricSummary,
+    /// Histogram over the range of values
+    pub area: MetricSummary,
+    /// Histogram over the range of finite values
+    pub radius_edge_ratio: MetricSummary,
+}
+
+impl Histogram {
+    /// Counts values into `bins` equal-width bins over `[start, end]`,
+    /// skipping NaN values
+    pub fn new(values: impl IntoIterator<Item = f64>, start: f64, end: f64, bins: usize) -> Self {
+        let mut counts = vec![0; bins.max(1)];
+        let last = counts.len() - 1;
+        let scale = if end > start {
+            counts.len() as f64 / (end - start)
+        } else {
+            0.0
+        };
+
+        for v in values {
+            if v.is_nan() {
+                continue;
+            }
+            let bin = Float::floor((v - start) * scale);
+            let bin = if bin < 0.0 {
+                0
+            } else {
+                (bin as usize).min(last)
+            };
+            counts[bin] += 1;
+        }
+
+        Histogram { start, end, counts }
+    }
+
+    /// Width of each bin
+    pub fn bin_width(&self) -> f64 {
+        (self.end - self.start) / self.counts.len() as f64
+    }
+}
+
+impl Delaunator {
+    /// Computes the shape measures of triangle `t`
+    pub fn triangle_quality(&self, t: usize) -> TriangleQuality {
+        let [a, b, c] = [0, 1, 2].map(|k| {
+            let i = self.triangles[3 * t + k] as usize;
+            (self.coords[2 * i], self.coords[2 * i + 1])
+        });
+
+        // Edge lengths, each opposite to the corner with the same position
+        let la = Float::hypot(c.0 - b.0, c.1 - b.1);
+        let lb = Float::hypot(a.0 - c.0, a.1 - c.1);
+        let lc = Float::hypot(b.0 - a.0, b.1 - a.1);
+
+        let area = orient2d(a.0, a.1, b.0, b.1, c.0, c.1).abs() / 2.0;
+        let angles = [angle(a, b, c), angle(b, c, a), angle(c, a, b)];
+
+        // R = abc / 4K and r = K / s with s the half perimeter
+        let circumradius = la * lb * lc / (4.0 * area);
+        let inradius = 2.0 * area / (la + lb + lc);
+
+        TriangleQuality {
+            min_angle: angles.iter().copied().fold(f64::INFINITY, f64::min),
+            max_angle: angles.iter().copied().fold(0.0, f64::max),
+            aspect_ratio: circumradius / (2.0 * inradius),
+            area,
+            radius_edge_ratio: circumradius / la.min(lb).min(lc),
+        }
+    }
+
+    /// Computes the shape measures of every triangle
+    pub fn quality(&self) -> Vec<TriangleQuality> {
+        (0..self.triangles.len() / 3)
+            .map(|t| self.triangle_quality(t))
+            .collect()
+    }
+
+    /// Summarizes the shape measures of all triangles with `bins` bins per
+    /// histogram
+    pub fn quality_report(&self, bins: usize) -> QualityReport {
+        let quality = self.quality();
+        let summary = |metric: fn(&TriangleQuality) -> f64, range: Option<(f64, f64)>| {
+            let values = || quality.iter().map(metric);
+            let (start, end) = range.unwrap_or_else(|| {
+                values()
+                    .filter(|v| v.is_finite())
+                    .fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), v| {
+                        (lo.min(v), hi.max(v))
+                    })
+            });
+            let (start, end) = if start <= end {
+                (start, end)
+            } else {
+                (0.0, 0.0)
+            };
+
+            MetricSummary {
+                min: values().fold(f64::NAN, f64::min),
+                max: values().fold(f64::NAN, f64::max),
+                mean: values().sum::<f64>() / quality.len() as f64,
+                histogram: Histogram::new(values(), start, end, bins),
+            }
+        };
+
+        QualityReport {
+            min_angle: summary(|q| q.min_angle, Some((0.0, 180.0))),
+            max_angle: summary(|q| q.max_angle, Some((0.0, 180.0))),
+            aspect_ratio: summary(|q| q.aspect_ratio, None),
+            area: summary(|q| q.area, None),
+            radius_edge_ratio: summary(|q| q.radius_edge_ratio, None),
+        }
+    }
+}
+
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+impl Delaunator {
+    /// Computes the shape measures of every triangle
+    ///
+    /// Returns 5 values per triangle: minimum angle, maximum angle (both in
+    /// degrees), aspect ratio, area and circumradius-to-shortest-edge ratio.
+    #[cfg_attr(feature = "wasm", wasm_bindgen(js_name = "triangleQuality"))]
+    pub fn triangle_quality_values(&self) -> Vec<f64> {
+        self.quality()
+            .iter()
+            .flat_map(|q| {
+                [
+                    q.min_angle,
+                    q.max_angle,
+                    q.aspect_ratio,
+                    q.area,
+                    q.radius_edge_ratio,
+                ]
+            })
+            .collect()
+    }
+}
+
+// Interior angle at corner a of the triangle abc, in degrees
+fn angle(a: (f64, f64), b: (f64, f64), c: (f64, f64)) -> f64 {
+    let (ux, uy) = (b.0 - a.0, b.1 - a.1);
+    let (vx, vy) = (c.0 - a.0, c.1 - a.1);
+    Float::atan2((ux * vy - uy * vx).abs(), ux * vx + uy * vy).to_degrees()
+}
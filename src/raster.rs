@@ -0,0 +1,199 @@
+//! Rasterization of triangulated surfaces
+//!
+//! This module turns per-point scalar values (elevations, temperatures, ...)
+//! into a regular grid by filling every triangle with a scanline pass and
+//! interpolating the values of its corners with barycentric weights.
+//! Grid cells whose center falls outside the convex hull are set to NaN.
+//!
+//! The resulting [`Grid`] can be written in the ESRI ASCII Grid (`.asc`)
+//! format understood by most GIS tools.
+
+use alloc::{vec, vec::Vec};
+
+#[cfg(feature = "std")]
+use std::io::{self, Write};
+
+use num_traits::Float;
+
+use crate::Delaunator;
+
+// Value written for NaN cells in ASCII Grid files
+#[cfg(feature = "std")]
+const ASC_NODATA: f64 = -9999.0;
+
+/// A regular grid of interpolated values
+///
+/// Values are stored row by row, starting with the top row (the one
+/// closest to `max_y`), which is the order used by ASCII Grid files.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Grid {
+    /// Number of columns
+    pub width: usize,
+    /// Number of rows
+    pub height: usize,
+    /// Area covered by the grid as `[min_x, min_y, max_x, max_y]`
+    pub bounds: [f64; 4],
+    /// Cell values, `width * height` entries in row-major order
+    pub data: Vec<f64>,
+}
+
+impl Grid {
+    /// Returns the width and height of a single cell
+    pub fn cell_size(&self) -> (f64, f64) {
+        let [min_x, min_y, max_x, max_y] = self.bounds;
+        (
+            (max_x - min_x) / self.width as f64,
+            (max_y - min_y) / self.height as f64,
+        )
+    }
+
+    /// Returns the value of the cell at the given column and row
+    ///
+    /// Row 0 is the top row of the grid.
+    pub fn get(&self, col: usize, row: usize) -> f64 {
+        self.data[row * self.width + col]
+    }
+
+    /// Writes the grid in ESRI ASCII Grid (`.asc`) format
+    ///
+    /// NaN cells are written as `-9999`, which is declared as the
+    /// `NODATA_value` of the file. Non-square cells are described with
+    /// the `dx`/`dy` header extension supported by GDAL. Only available
+    /// with the `std` feature.
+    #[cfg(feature = "std")]
+    pub fn write_asc<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        let (dx, dy) = self.cell_size();
+
+        writeln!(writer, "ncols {}", self.width)?;
+        writeln!(writer, "nrows {}", self.height)?;
+        writeln!(writer, "xllcorner {}", self.bounds[0])?;
+        writeln!(writer, "yllcorner {}", self.bounds[1])?;
+        if dx == dy {
+            writeln!(writer, "cellsize {}", dx)?;
+        } else {
+            writeln!(writer, "dx {}", dx)?;
+            writeln!(writer, "dy {}", dy)?;
+        }
+        writeln!(writer, "NODATA_value {}", ASC_NODATA)?;
+
+        for row in self.data.chunks(self.width.max(1)) {
+            for (i, &value) in row.iter().enumerate() {
+                if i > 0 {
+                    writer.write_all(b" ")?;
+                }
+                let value = if value.is_nan() { ASC_NODATA } else { value };
+                write!(writer, "{}", value)?;
+            }
+            writer.write_all(b"\n")?;
+        }
+
+        writer.flush()
+    }
+}
+
+impl Delaunator {
+    /// Rasterizes per-point values over the triangulation
+    ///
+    /// `values` holds one value per input point. The grid covers `bounds`
+    /// (`[min_x, min_y, max_x, max_y]`) with `width` columns and `height`
+    /// rows, and each cell is sampled at its center. Cells outside the
+    /// convex hull are NaN.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `values` does not contain exactly one value per point.
+    pub fn rasterize(&self, values: &[f64], bounds: [f64; 4], width: usize, height: usize) -> Grid {
+        assert_eq!(
+            values.len(),
+            self.coords.len() / 2,
+            "expected one value per point"
+        );
+
+        let mut grid = Grid {
+            width,
+            height,
+            bounds,
+            data: vec![f64::NAN; width * height],
+        };
+
+        if width == 0 || height == 0 {
+            return grid;
+        }
+
+        let (dx, dy) = grid.cell_size();
+        let [min_x, _, _, max_y] = bounds;
+
+        for t in self.triangles.chunks_exact(3) {
+            let (a, b, c) = (t[0] as usize, t[1] as usize, t[2] as usize);
+            let (ax, ay) = (self.coords[2 * a], self.coords[2 * a + 1]);
+            let (bx, by) = (self.coords[2 * b], self.coords[2 * b + 1]);
+            let (cx, cy) = (self.coords[2 * c], self.coords[2 * c + 1]);
+
+            // Denominator of the barycentric coordinates (twice the signed area)
+            let det = (by - cy) * (ax - cx) + (cx - bx) * (ay - cy);
+            if det == 0.0 {
+                continue;
+            }
+
+            // Rows whose centers lie within the vertical extent of the triangle
+            let top = ay.max(by).max(cy);
+            let bottom = ay.min(by).min(cy);
+            let Some((row_start, row_end)) = cell_range(
+                (max_y - top) / dy - 0.5,
+                (max_y - bottom) / dy - 0.5,
+                height,
+            ) else {
+                continue;
+            };
+
+            let edges = [(ax, ay, bx, by), (bx, by, cx, cy), (cx, cy, ax, ay)];
+
+            for row in row_start..=row_end {
+                let y = max_y - (row as f64 + 0.5) * dy;
+
+                // Intersect the scanline with the triangle edges
+                let mut left = f64::INFINITY;
+                let mut right = f64::NEG_INFINITY;
+                for &(px, py, qx, qy) in &edges {
+                    if py == qy || y < py.min(qy) || y > py.max(qy) {
+                        continue;
+                    }
+                    let x = px + (y - py) * (qx - px) / (qy - py);
+                    left = left.min(x);
+                    right = right.max(x);
+                }
+
+                let Some((col_start, col_end)) =
+                    cell_range((left - min_x) / dx - 0.5, (right - min_x) / dx - 0.5, width)
+                else {
+                    continue;
+                };
+
+                for col in col_start..=col_end {
+                    let x = min_x + (col as f64 + 0.5) * dx;
+
+                    let l0 = ((by - cy) * (x - cx) + (cx - bx) * (y - cy)) / det;
+                    let l1 = ((cy - ay) * (x - cx) + (ax - cx) * (y - cy)) / det;
+                    let l2 = 1.0 - l0 - l1;
+
+                    grid.data[row * width + col] = l0 * values[a] + l1 * values[b] + l2 * values[c];
+                }
+            }
+        }
+
+        grid
+    }
+}
+
+/// Converts a continuous range of cell coordinates into the inclusive range
+/// of cell indices whose centers it covers, clamped to `0..len`
+fn cell_range(start: f64, end: f64, len: usize) -> Option<(usize, usize)> {
+    let first = Float::ceil(start).max(0.0);
+    let last = Float::floor(end).min(len as f64 - 1.0);
+
+    if first.is_nan() || last.is_nan() || first > last {
+        return None;
+    }
+
+    Some((first as usize, last as usize))
+}
@@ -0,0 +1,594 @@
+//! Weighted Delaunay (regular) triangulation and power diagrams
+//!
+//! Giving every point a weight `w` turns the Delaunay triangulation into the
+//! regular triangulation: the projection of the lower convex hull of the
+//! points lifted to `(x, y, x² + y² - w)`. Its dual is the power diagram (or
+//! Laguerre diagram), where each point owns the region of the plane with the
+//! lowest power distance `|p - q|² - w` to it. With equal weights both reduce
+//! to the Delaunay triangulation and the Voronoi diagram.
+//!
+//! A point whose lifted image lies above the hull of the others has an empty
+//! power cell. Such a point is *hidden*: it is left out of the triangulation
+//! and reported in [`Delaunator::hidden`].
+//!
+//! Hidden points rule out the sweep used for the unweighted case, so the
+//! regular triangulation is built incrementally with the Bowyer-Watson
+//! algorithm, which also serves the unweighted case for the spatially
+//! coherent [`Order`]s. Points are inserted along a Hilbert curve, or in
+//! biased randomized rounds, each insertion walking from the previous one
+//! to find its triangle and replacing the triangles whose lifted in-circle
+//! (power) test fails. The test is exact, so that the cavity of every
+//! insertion is star shaped around the new point. The outside of the hull
+//! is covered with ghost triangles joining each hull edge to a virtual
+//! point at infinity, which makes points beyond the hull ordinary
+//! insertions.
+
+use alloc::{vec, vec::Vec};
+
+use num_traits::Float;
+use robust::{incircle, Coord};
+#[cfg(feature = "wasm")]
+use wasm_bindgen::prelude::*;
+
+use crate::order::{brio_order, hilbert_order};
+use crate::power::power;
+use crate::{next_halfedge, orient2d, prev_halfedge, Delaunator, Error, Order};
+
+// Virtual point at infinity, the third corner of ghost triangles
+const INFINITE: u32 = u32::MAX;
+
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+impl Delaunator {
+    /// Computes the orthocenter of every triangle
+    ///
+    /// The orthocenter is the point with the same power distance to the
+    /// three corners of a triangle. Returns `[x0, y0, x1, y1, ...]`, one pair
+    /// per triangle; these are the vertices of the power diagram and match
+    /// the circumcenters when the points are unweighted.
+    #[cfg_attr(feature = "wasm", wasm_bindgen(js_name = "orthocenters"))]
+    pub fn orthocenters(&self) -> Vec<f64> {
+        self.triangles
+            .chunks_exact(3)
+            .flat_map(|t| {
+                let (x, y) = self.orthocenter(t[0] as usize, t[1] as usize, t[2] as usize);
+                [x, y]
+            })
+            .collect()
+    }
+}
+
+impl Delaunator {
+    /// Computes the power diagram cell of point `i` clipped to a rectangle
+    ///
+    /// `bounds` is `[min_x, min_y, max_x, max_y]`. Returns the cell as a
+    /// closed polygon `[x0, y0, x1, y1, ...]` winding counterclockwise with
+    /// the y axis pointing up, opposite to the triangles. The cell is empty
+    /// for hidden points and points left out of the triangulation. Without
+    /// weights this is the Voronoi cell of the point.
+    pub fn power_cell(&self, i: usize, bounds: [f64; 4]) -> Vec<f64> {
+        let inedges = self.inedges();
+        self.clipped_cell(&inedges, i, bounds)
+    }
+
+    /// Computes the power diagram cells of all points clipped to a rectangle
+    ///
+    /// Same as calling [`Delaunator::power_cell`] for every point, but walks
+    /// the triangulation only once.
+    pub fn power_cells(&self, bounds: [f64; 4]) -> Vec<Vec<f64>> {
+        let inedges = self.inedges();
+        (0..self.coords.len() / 2)
+            .map(|i| self.clipped_cell(&inedges, i, bounds))
+            .collect()
+    }
+
+    pub(crate) fn weight(&self, i: usize) -> f64 {
+        if self.weights.is_empty() {
+            0.0
+        } else {
+            self.weights[i]
+        }
+    }
+
+    // Center of the circle orthogonal to the weighted points a, b and c
+    pub(crate) fn orthocenter(&self, a: usize, b: usize, c: usize) -> (f64, f64) {
+        let (ax, ay) = (self.coords[2 * a], self.coords[2 * a + 1]);
+        let dx = self.coords[2 * b] - ax;
+        let dy = self.coords[2 * b + 1] - ay;
+        let ex = self.coords[2 * c] - ax;
+        let ey = self.coords[2 * c + 1] - ay;
+
+        let wa = self.weight(a);
+        let bl = dx * dx + dy * dy - self.weight(b) + wa;
+        let cl = ex * ex + ey * ey - self.weight(c) + wa;
+        let d = 0.5 / (dx * ey - dy * ex);
+
+        (ax + (ey * bl - dy * cl) * d, ay + (dx * cl - ex * bl) * d)
+    }
+
+    fn clipped_cell(&self, inedges: &[i32], i: usize, bounds: [f64; 4]) -> Vec<f64> {
+        let e0 = inedges[i];
+        if e0 == -1 {
+            return Vec::new();
+        }
+
+        // Orthocenters of the triangles around i, in winding order
+        let mut cell = Vec::new();
+        let mut e = e0 as usize;
+        let last = loop {
+            let t = e / 3;
+            let [a, b, c] = [0, 1, 2].map(|k| self.triangles[3 * t + k] as usize);
+            let (x, y) = self.orthocenter(a, b, c);
+            cell.extend_from_slice(&[x, y]);
+
+            let outgoing = next_halfedge(e);
+            let twin = self.halfedges[outgoing];
+            if twin == -1 {
+                break Some(outgoing);
+            }
+            e = twin as usize;
+            if e == e0 as usize {
+                break None;
+            }
+        };
+
+        // Cells of hull points are unbounded, bounded by rays leaving the
+        // first and last orthocenters across the hull edges at i. Replace
+        // them with points far enough to lie outside of the rectangle.
+        if let Some(outgoing) = last {
+            let [min_x, min_y, max_x, max_y] = bounds;
+            let mut reach = (max_x - min_x).abs() + (max_y - min_y).abs();
+            for p in cell.chunks_exact(2) {
+                reach = reach
+                    .max((p[0] - min_x).abs())
+                    .max((p[0] - max_x).abs())
+                    .max((p[1] - min_y).abs())
+                    .max((p[1] - max_y).abs());
+            }
+            let reach = 4.0 * reach;
+
+            let n_in = self.hull_normal(e0 as usize);
+            let n_out = self.hull_normal(outgoing);
+            let (fx, fy) = (cell[0], cell[1]);
+            let (lx, ly) = (cell[cell.len() - 2], cell[cell.len() - 1]);
+
+            // The middle point keeps the far side of the polygon away from
+            // the rectangle when the two rays point in opposite directions
+            let mx = n_in.0 + n_out.0;
+            let my = n_in.1 + n_out.1;
+            let m = Float::sqrt(mx * mx + my * my);
+            let mut far = vec![lx + n_out.0 * reach, ly + n_out.1 * reach];
+            if m > 0.0 {
+                far.push((fx + lx) / 2.0 + mx / m * reach);
+                far.push((fy + ly) / 2.0 + my / m * reach);
+            }
+            far.extend_from_slice(&[fx + n_in.0 * reach, fy + n_in.1 * reach]);
+
+            cell.extend_from_slice(&far);
+        }
+
+        clip_to_rectangle(&cell, bounds)
+    }
+
+    // Unit normal of the hull edge of halfedge e, pointing out of the hull
+    fn hull_normal(&self, e: usize) -> (f64, f64) {
+        let a = self.triangles[e] as usize;
+        let b = self.triangles[next_halfedge(e)] as usize;
+        let dx = self.coords[2 * b] - self.coords[2 * a];
+        let dy = self.coords[2 * b + 1] - self.coords[2 * a + 1];
+        let len = Float::sqrt(dx * dx + dy * dy);
+        (-dy / len, dx / len)
+    }
+
+    // Builds the regular triangulation of the weighted points, or the
+    // Delaunay triangulation without weights, by incremental insertion.
+    // Returns false without touching the output when all points are
+    // collinear, which is left to the sweep.
+    pub(crate) fn update_regular(&mut self) -> Result<bool, Error> {
+        let mut order = match self.options.order {
+            Order::Brio => brio_order(&self.coords),
+            Order::Sweep | Order::Hilbert => hilbert_order(&self.coords),
+        };
+        let Some(seeds) = find_seeds(&self.coords, &order) else {
+            return Ok(false);
+        };
+        order.retain(|&i| !seeds.contains(&i));
+
+        let mut mesh = Mesh::new(&self.coords, &self.weights, seeds);
+        for &i in &order {
+            mesh.insert(i)?;
+        }
+
+        // Without weights, only duplicates are left out, as by the sweep
+        let (triangles, halfedges, hull) = mesh.extract();
+        if !self.weights.is_empty() {
+            self.hidden = (0..mesh.hidden.len() as u32)
+                .filter(|&i| mesh.hidden[i as usize])
+                .collect();
+        }
+        self.triangles = triangles;
+        self.halfedges = halfedges;
+        self.hull = hull;
+        Ok(true)
+    }
+}
+
+// Triangulation under construction. Every halfedge has a twin thanks to the
+// ghost triangles, and triangles removed by an insertion leave free slots
+// that later insertions reuse.
+struct Mesh<'a> {
+    coords: &'a [f64],
+    weights: &'a [f64],
+
+    triangles: Vec<u32>,
+    halfedges: Vec<usize>,
+    free: Vec<usize>,
+    hidden: Vec<bool>,
+
+    // Triangle the next point location starts from
+    last: usize,
+    // Rotates the edge tested first by the walk, which can otherwise cycle
+    walk_turn: usize,
+
+    // Per triangle, the last insertion that tested it, times two, plus one
+    // when it conflicted with the inserted point
+    marks: Vec<u32>,
+    stamp: u32,
+
+    // Scratch buffers reused by every insertion
+    cavity: Vec<usize>,
+    stack: Vec<usize>,
+    boundary: Vec<(u32, u32, usize)>,
+    created: Vec<usize>,
+}
+
+impl<'a> Mesh<'a> {
+    // Starts from the triangle of three non-collinear points and the three
+    // ghost triangles around it
+    fn new(coords: &'a [f64], weights: &'a [f64], seeds: [u32; 3]) -> Self {
+        let n = coords.len() / 2;
+        let [a, mut b, mut c] = seeds;
+        if orient(coords, a, b, c) < 0.0 {
+            core::mem::swap(&mut b, &mut c);
+        }
+
+        let mut mesh = Mesh {
+            coords,
+            weights,
+            triangles: Vec::with_capacity(6 * n + 6),
+            halfedges: Vec::with_capacity(6 * n + 6),
+            free: Vec::new(),
+            hidden: vec![false; n],
+            last: 0,
+            walk_turn: 0,
+            marks: Vec::with_capacity(2 * n + 2),
+            stamp: 0,
+            cavity: Vec::new(),
+            stack: Vec::new(),
+            boundary: Vec::new(),
+            created: Vec::new(),
+        };
+
+        // Triangle 0 is (a, b, c) and triangle 1 + k is the ghost across its
+        // edge k, going from u to v: (v, u, INFINITE)
+        mesh.triangles.extend_from_slice(&[a, b, c]);
+        mesh.halfedges.extend_from_slice(&[3, 6, 9]);
+        mesh.marks.push(0);
+        for k in 0..3 {
+            let u = mesh.triangles[k];
+            let v = mesh.triangles[(k + 1) % 3];
+            // The edge u -> INFINITE pairs with INFINITE -> u in the ghost of
+            // the previous edge
+            let prev = 3 * ((k + 2) % 3 + 1);
+            mesh.triangles.extend_from_slice(&[v, u, INFINITE]);
+            mesh.halfedges
+                .extend_from_slice(&[k, prev + 2, 3 * ((k + 1) % 3 + 1) + 1]);
+            mesh.marks.push(0);
+        }
+
+        mesh
+    }
+
+    fn point(&self, i: u32) -> (f64, f64) {
+        let i = i as usize;
+        (self.coords[2 * i], self.coords[2 * i + 1])
+    }
+
+    fn weight(&self, i: u32) -> f64 {
+        self.weights[i as usize]
+    }
+
+    fn is_ghost(&self, t: usize) -> bool {
+        self.triangles[3 * t..3 * t + 3].contains(&INFINITE)
+    }
+
+    // Fails when the cavity is not star shaped around the new point, which
+    // only wrong predicate results can cause, as when lifting overflows
+    fn insert(&mut self, i: u32) -> Result<(), Error> {
+        let t = self.locate(i);
+        if !self.conflicts(t, i) {
+            self.hidden[i as usize] = true;
+            return Ok(());
+        }
+
+        // Grow the cavity of triangles in conflict with the new point and
+        // collect the halfedges on its boundary
+        self.stamp += 1;
+        let (tested, conflicted) = (2 * self.stamp, 2 * self.stamp + 1);
+        self.cavity.clear();
+        self.boundary.clear();
+        self.marks[t] = conflicted;
+        self.cavity.push(t);
+        self.stack.push(t);
+
+        while let Some(t) = self.stack.pop() {
+            for e in 3 * t..3 * t + 3 {
+                let o = self.halfedges[e] / 3;
+                let mark = self.marks[o];
+                let inside = if mark == conflicted {
+                    true
+                } else if mark == tested {
+                    false
+                } else if self.conflicts(o, i) {
+                    self.marks[o] = conflicted;
+                    self.cavity.push(o);
+                    self.stack.push(o);
+                    true
+                } else {
+                    self.marks[o] = tested;
+                    false
+                };
+
+                if !inside {
+                    let u = self.triangles[e];
+                    let v = self.triangles[next_halfedge(e)];
+                    self.boundary.push((u, v, self.halfedges[e]));
+                }
+            }
+        }
+
+        // Corners of the cavity that are not on its boundary disappear from
+        // the triangulation: the new point hides them
+        for k in 0..self.cavity.len() {
+            let t = self.cavity[k];
+            for e in 3 * t..3 * t + 3 {
+                let v = self.triangles[e];
+                if v != INFINITE && !self.boundary.iter().any(|b| b.0 == v) {
+                    self.hidden[v as usize] = true;
+                }
+            }
+        }
+        self.free.extend_from_slice(&self.cavity);
+
+        // Join every boundary edge to the new point. The cavity is star
+        // shaped around it, so the new triangles fan around the point and the
+        // one starting at v follows the one ending at v.
+        self.created.clear();
+        for k in 0..self.boundary.len() {
+            let (u, v, outside) = self.boundary[k];
+            let t = self.allocate();
+            self.triangles[3 * t..3 * t + 3].copy_from_slice(&[u, v, i]);
+            self.halfedges[3 * t] = outside;
+            self.halfedges[outside] = 3 * t;
+            self.created.push(t);
+        }
+        for k in 0..self.created.len() {
+            let t = self.created[k];
+            let v = self.boundary[k].1;
+            let next = self
+                .boundary
+                .iter()
+                .position(|b| b.0 == v)
+                .ok_or(Error::InvalidTriangulation)?;
+            let s = self.created[next];
+            self.halfedges[3 * t + 1] = 3 * s + 2;
+            self.halfedges[3 * s + 2] = 3 * t + 1;
+        }
+
+        self.last = self
+            .created
+            .iter()
+            .copied()
+            .find(|&t| !self.is_ghost(t))
+            .unwrap_or(self.created[0]);
+        Ok(())
+    }
+
+    fn allocate(&mut self) -> usize {
+        match self.free.pop() {
+            Some(t) => t,
+            None => {
+                self.triangles.extend_from_slice(&[0; 3]);
+                self.halfedges.extend_from_slice(&[0; 3]);
+                self.marks.push(0);
+                self.marks.len() - 1
+            }
+        }
+    }
+
+    // Finds the triangle containing point i, or a ghost triangle whose hull
+    // edge has i strictly outside, by walking across the edges i lies beyond
+    fn locate(&mut self, i: u32) -> usize {
+        let (px, py) = self.point(i);
+        let mut t = self.last;
+        if self.is_ghost(t) {
+            let k = self.ghost_edge(t);
+            t = self.halfedges[k] / 3;
+        }
+
+        'walk: loop {
+            self.walk_turn = (self.walk_turn + 1) % 3;
+            for j in 0..3 {
+                let e = 3 * t + (self.walk_turn + j) % 3;
+                let (ax, ay) = self.point(self.triangles[e]);
+                let (bx, by) = self.point(self.triangles[next_halfedge(e)]);
+                if orient2d(ax, ay, bx, by, px, py) < 0.0 {
+                    t = self.halfedges[e] / 3;
+                    if self.is_ghost(t) {
+                        return t;
+                    }
+                    continue 'walk;
+                }
+            }
+            return t;
+        }
+    }
+
+    // Halfedge of the finite edge of ghost triangle t
+    fn ghost_edge(&self, t: usize) -> usize {
+        let k = (0..3)
+            .find(|&k| self.triangles[3 * t + k] == INFINITE)
+            .unwrap();
+        3 * t + (k + 1) % 3
+    }
+
+    // Whether inserting point i removes triangle t
+    fn conflicts(&self, t: usize, i: u32) -> bool {
+        let p = self.point(i);
+
+        if !self.is_ghost(t) {
+            let [a, b, c] = [0, 1, 2].map(|k| self.triangles[3 * t + k]);
+            if self.weights.is_empty() {
+                // Exact, so that cocircular points never conflict
+                let [a, b, c, p] =
+                    [self.point(a), self.point(b), self.point(c), p].map(|(x, y)| Coord { x, y });
+                return incircle(a, b, c, p) < 0.0;
+            }
+            let w = [a, b, c, i].map(|j| self.weight(j));
+            return power(self.point(a), self.point(b), self.point(c), p, w) < 0.0;
+        }
+
+        // A ghost conflicts with the points beyond its hull edge, as well as
+        // with points on the line of the edge when these remove the triangle
+        // inside the hull. Both then go, so that no new triangle is flat:
+        // on that line, the plane of the triangle is the lifted edge.
+        let e = self.ghost_edge(t);
+        let a = self.point(self.triangles[e]);
+        let b = self.point(self.triangles[next_halfedge(e)]);
+        let side = orient2d(a.0, a.1, b.0, b.1, p.0, p.1);
+        if side != 0.0 {
+            return side > 0.0;
+        }
+        self.conflicts(self.halfedges[e] / 3, i)
+    }
+
+    // Compacts the finite triangles into the output arrays and reads the hull
+    // off the ghost triangles
+    fn extract(&self) -> (Vec<u32>, Vec<i32>, Vec<u32>) {
+        let num_slots = self.triangles.len() / 3;
+        let mut is_free = vec![false; num_slots];
+        for &t in &self.free {
+            is_free[t] = true;
+        }
+
+        let mut ids = vec![usize::MAX; num_slots];
+        let mut count = 0;
+        for t in 0..num_slots {
+            if !is_free[t] && !self.is_ghost(t) {
+                ids[t] = count;
+                count += 1;
+            }
+        }
+
+        let mut triangles = Vec::with_capacity(3 * count);
+        let mut halfedges = Vec::with_capacity(3 * count);
+        let mut ghost = None;
+        for t in 0..num_slots {
+            if is_free[t] {
+                continue;
+            }
+            if ids[t] == usize::MAX {
+                ghost = Some(t);
+                continue;
+            }
+            for e in 3 * t..3 * t + 3 {
+                triangles.push(self.triangles[e]);
+                let twin = self.halfedges[e];
+                let id = ids[twin / 3];
+                halfedges.push(if id == usize::MAX {
+                    -1
+                } else {
+                    (3 * id + twin % 3) as i32
+                });
+            }
+        }
+
+        // The ghost (v, u, INFINITE) lies across the hull edge u -> v, and
+        // the twin of its edge INFINITE -> v belongs to the ghost across the
+        // hull edge leaving v
+        let mut hull = Vec::new();
+        if let Some(start) = ghost {
+            let mut t = start;
+            loop {
+                let e = self.ghost_edge(t);
+                hull.push(self.triangles[next_halfedge(e)]);
+                t = self.halfedges[prev_halfedge(e)] / 3;
+                if t == start {
+                    break;
+                }
+            }
+        }
+
+        (triangles, halfedges, hull)
+    }
+}
+
+fn orient(coords: &[f64], a: u32, b: u32, c: u32) -> f64 {
+    let [a, b, c] = [a, b, c].map(|i| i as usize);
+    orient2d(
+        coords[2 * a],
+        coords[2 * a + 1],
+        coords[2 * b],
+        coords[2 * b + 1],
+        coords[2 * c],
+        coords[2 * c + 1],
+    )
+}
+
+// Picks three non-collinear points, starting from the beginning of the
+// insertion order so that the first triangle is small
+fn find_seeds(coords: &[f64], order: &[u32]) -> Option<[u32; 3]> {
+    let a = *order.first()?;
+    let b = *order.iter().find(|&&i| {
+        coords[2 * i as usize] != coords[2 * a as usize]
+            || coords[2 * i as usize + 1] != coords[2 * a as usize + 1]
+    })?;
+    let c = *order.iter().find(|&&i| orient(coords, a, b, i) != 0.0)?;
+    Some([a, b, c])
+}
+
+// Sutherland-Hodgman clipping of a convex polygon to a rectangle
+fn clip_to_rectangle(polygon: &[f64], bounds: [f64; 4]) -> Vec<f64> {
+    let [min_x, min_y, max_x, max_y] = bounds;
+    let mut points = polygon.to_vec();
+
+    // Each side keeps the points where inside(x, y) >= 0
+    let sides: [&dyn Fn(f64, f64) -> f64; 4] = [
+        &|x, _| x - min_x,
+        &|x, _| max_x - x,
+        &|_, y| y - min_y,
+        &|_, y| max_y - y,
+    ];
+
+    for inside in sides {
+        let mut clipped = Vec::with_capacity(points.len() + 2);
+        let n = points.len() / 2;
+        for k in 0..n {
+            let (ax, ay) = (points[2 * k], points[2 * k + 1]);
+            let (bx, by) = (points[2 * ((k + 1) % n)], points[2 * ((k + 1) % n) + 1]);
+            let (da, db) = (inside(ax, ay), inside(bx, by));
+            if da >= 0.0 {
+                clipped.extend_from_slice(&[ax, ay]);
+            }
+            if (da >= 0.0) != (db >= 0.0) {
+                let t = da / (da - db);
+                clipped.extend_from_slice(&[ax + t * (bx - ax), ay + t * (by - ay)]);
+            }
+        }
+        points = clipped;
+        if points.is_empty() {
+            break;
+        }
+    }
+
+    points
+}
@@ -0,0 +1,285 @@
+//! Linear passes over the points
+//!
+//! Before the sweep, `update` scans every point to find the bounding box,
+//! the three seed points and the distances to the seed circumcenter. Each
+//! pass runs over blocks of points, with portable SIMD inside a block under
+//! the `simd` feature and blocks spread over threads under the `rayon`
+//! feature. Every lane computes exactly what the scalar code does, and ties
+//! keep the lowest index, so all four combinations give the same results.
+
+use cfg_if::cfg_if;
+
+use crate::{circumradius, dist};
+
+/// Bounding box of points as `(min_x, min_y, max_x, max_y)`
+pub(crate) type Bounds = (f64, f64, f64, f64);
+
+/// Quantity minimized by a seed search
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum Metric {
+    /// Squared distance to `(x, y)`, leaving out points at `(x, y)` when
+    /// `skip_zero` is set
+    Dist { x: f64, y: f64, skip_zero: bool },
+    /// Squared circumradius of the triangle formed with points `i` at `a`
+    /// and `j` at `b`, leaving out `i` and `j`
+    Circumradius {
+        i: usize,
+        j: usize,
+        a: (f64, f64),
+        b: (f64, f64),
+    },
+}
+
+impl Metric {
+    // Value for point i at (x, y), infinite for points left out
+    #[inline]
+    fn eval(self, i: usize, x: f64, y: f64) -> f64 {
+        match self {
+            Metric::Dist {
+                x: cx,
+                y: cy,
+                skip_zero,
+            } => {
+                let d = dist(cx, cy, x, y);
+                if skip_zero && d <= 0.0 {
+                    f64::INFINITY
+                } else {
+                    d
+                }
+            }
+            Metric::Circumradius { i: a, j: b, .. } if i == a || i == b => f64::INFINITY,
+            Metric::Circumradius { a, b, .. } => circumradius(a.0, a.1, b.0, b.1, x, y),
+        }
+    }
+}
+
+cfg_if! {
+    if #[cfg(feature = "rayon")] {
+        use rayon::prelude::*;
+
+        // Points per rayon task, below which splitting costs more than the
+        // work it spreads
+        const MIN_LEN: usize = 1 << 12;
+
+        /// Calculate the bounding box of the points
+        pub(crate) fn bounding_box(coords: &[f64]) -> Bounds {
+            coords
+                .par_chunks(2 * MIN_LEN)
+                .map(block_box)
+                .reduce(empty_box, union)
+        }
+
+        /// Find the point minimizing `metric`, with its value
+        ///
+        /// Ties keep the lowest index. Returns `(0, f64::INFINITY)` if no
+        /// value is finite.
+        pub(crate) fn argmin(coords: &[f64], metric: Metric) -> (usize, f64) {
+            coords
+                .par_chunks(2 * MIN_LEN)
+                .enumerate()
+                .map(|(k, block)| block_argmin(block, k * MIN_LEN, metric))
+                .reduce(|| (0, f64::INFINITY), first_min)
+        }
+
+        /// Fill `dists` with the squared distance of every point to `center`
+        pub(crate) fn distances(coords: &[f64], center: (f64, f64), dists: &mut [f64]) {
+            dists
+                .par_chunks_mut(MIN_LEN)
+                .zip(coords.par_chunks(2 * MIN_LEN))
+                .for_each(|(dists, block)| block_distances(block, center, dists));
+        }
+    } else {
+        /// Calculate the bounding box of the points
+        pub(crate) fn bounding_box(coords: &[f64]) -> Bounds {
+            block_box(coords)
+        }
+
+        /// Find the point minimizing `metric`, with its value
+        ///
+        /// Ties keep the lowest index. Returns `(0, f64::INFINITY)` if no
+        /// value is finite.
+        pub(crate) fn argmin(coords: &[f64], metric: Metric) -> (usize, f64) {
+            block_argmin(coords, 0, metric)
+        }
+
+        /// Fill `dists` with the squared distance of every point to `center`
+        pub(crate) fn distances(coords: &[f64], center: (f64, f64), dists: &mut [f64]) {
+            block_distances(coords, center, dists);
+        }
+    }
+}
+
+cfg_if! {
+    if #[cfg(feature = "simd")] {
+        use wide::{f64x4, CmpEq, CmpLe, CmpLt};
+
+        // Deinterleaves 4 points into their x and y coordinates
+        #[inline]
+        fn load(c: &[f64]) -> (f64x4, f64x4) {
+            (
+                f64x4::new([c[0], c[2], c[4], c[6]]),
+                f64x4::new([c[1], c[3], c[5], c[7]]),
+            )
+        }
+
+        impl Metric {
+            // Values for 4 points, numbered by `ids`
+            #[inline]
+            fn eval4(self, ids: f64x4, x: f64x4, y: f64x4) -> f64x4 {
+                let infinity = f64x4::splat(f64::INFINITY);
+                match self {
+                    Metric::Dist {
+                        x: cx,
+                        y: cy,
+                        skip_zero,
+                    } => {
+                        let dx = f64x4::splat(cx) - x;
+                        let dy = f64x4::splat(cy) - y;
+                        let d = dx * dx + dy * dy;
+                        if skip_zero {
+                            d.cmp_le(f64x4::splat(0.0)).blend(infinity, d)
+                        } else {
+                            d
+                        }
+                    }
+                    Metric::Circumradius { i, j, a, b } => {
+                        // Same operations as `circumradius`, lane by lane
+                        let dx = b.0 - a.0;
+                        let dy = b.1 - a.1;
+                        let ex = x - f64x4::splat(a.0);
+                        let ey = y - f64x4::splat(a.1);
+
+                        let bl = f64x4::splat(dx * dx + dy * dy);
+                        let cl = ex * ex + ey * ey;
+                        let (dx, dy) = (f64x4::splat(dx), f64x4::splat(dy));
+                        let d = f64x4::splat(0.5) / (dx * ey - dy * ex);
+
+                        let x = (ey * bl - dy * cl) * d;
+                        let y = (dx * cl - ex * bl) * d;
+
+                        let skipped = ids.cmp_eq(f64x4::splat(i as f64))
+                            | ids.cmp_eq(f64x4::splat(j as f64));
+                        skipped.blend(infinity, x * x + y * y)
+                    }
+                }
+            }
+        }
+
+        fn block_box(coords: &[f64]) -> Bounds {
+            let mut min = (f64x4::splat(f64::INFINITY), f64x4::splat(f64::INFINITY));
+            let mut max = (
+                f64x4::splat(f64::NEG_INFINITY),
+                f64x4::splat(f64::NEG_INFINITY),
+            );
+            let blocks = coords.chunks_exact(8);
+            let rest = blocks.remainder();
+            for c in blocks {
+                let (x, y) = load(c);
+                min = (min.0.min(x), min.1.min(y));
+                max = (max.0.max(x), max.1.max(y));
+            }
+
+            let [min_x, min_y, max_x, max_y] =
+                [min.0, min.1, max.0, max.1].map(|v| v.to_array());
+            (0..4)
+                .map(|k| (min_x[k], min_y[k], max_x[k], max_y[k]))
+                .chain(rest.chunks_exact(2).map(|p| (p[0], p[1], p[0], p[1])))
+                .fold(empty_box(), union)
+        }
+
+        fn block_argmin(coords: &[f64], start: usize, metric: Metric) -> (usize, f64) {
+            let mut best = f64x4::splat(f64::INFINITY);
+            let mut best_ids = f64x4::splat(0.0);
+            let mut ids = f64x4::new([0.0, 1.0, 2.0, 3.0]) + f64x4::splat(start as f64);
+            let blocks = coords.chunks_exact(8);
+            let rest = blocks.remainder();
+            for c in blocks {
+                let (x, y) = load(c);
+                let v = metric.eval4(ids, x, y);
+                let smaller = v.cmp_lt(best);
+                best = smaller.blend(v, best);
+                best_ids = smaller.blend(ids, best_ids);
+                ids += f64x4::splat(4.0);
+            }
+
+            let rest_start = start + (coords.len() - rest.len()) / 2;
+            let lanes = best_ids
+                .to_array()
+                .into_iter()
+                .map(|i| i as usize)
+                .zip(best.to_array());
+            rest.chunks_exact(2)
+                .enumerate()
+                .map(|(k, p)| {
+                    let i = rest_start + k;
+                    (i, metric.eval(i, p[0], p[1]))
+                })
+                .chain(lanes)
+                .fold((0, f64::INFINITY), first_min)
+        }
+
+        fn block_distances(coords: &[f64], center: (f64, f64), dists: &mut [f64]) {
+            let (cx, cy) = (f64x4::splat(center.0), f64x4::splat(center.1));
+            let blocks = coords.chunks_exact(8);
+            let rest = blocks.remainder();
+            let mut out = dists.chunks_exact_mut(4);
+            for (c, d) in blocks.zip(&mut out) {
+                let (x, y) = load(c);
+                let (dx, dy) = (x - cx, y - cy);
+                d.copy_from_slice(&(dx * dx + dy * dy).to_array());
+            }
+            for (d, p) in out.into_remainder().iter_mut().zip(rest.chunks_exact(2)) {
+                *d = dist(p[0], p[1], center.0, center.1);
+            }
+        }
+    } else {
+        fn block_box(coords: &[f64]) -> Bounds {
+            coords
+                .chunks_exact(2)
+                .map(|p| (p[0], p[1], p[0], p[1]))
+                .fold(empty_box(), union)
+        }
+
+        fn block_argmin(coords: &[f64], start: usize, metric: Metric) -> (usize, f64) {
+            coords
+                .chunks_exact(2)
+                .enumerate()
+                .map(|(k, p)| {
+                    let i = start + k;
+                    (i, metric.eval(i, p[0], p[1]))
+                })
+                .fold((0, f64::INFINITY), first_min)
+        }
+
+        fn block_distances(coords: &[f64], center: (f64, f64), dists: &mut [f64]) {
+            for (d, p) in dists.iter_mut().zip(coords.chunks_exact(2)) {
+                *d = dist(p[0], p[1], center.0, center.1);
+            }
+        }
+    }
+}
+
+#[inline]
+fn empty_box() -> Bounds {
+    (
+        f64::INFINITY,
+        f64::INFINITY,
+        f64::NEG_INFINITY,
+        f64::NEG_INFINITY,
+    )
+}
+
+#[inline]
+fn union(a: Bounds, b: Bounds) -> Bounds {
+    (a.0.min(b.0), a.1.min(b.1), a.2.max(b.2), a.3.max(b.3))
+}
+
+// The candidate with the smaller value, or the lower index on ties
+#[inline]
+fn first_min(a: (usize, f64), b: (usize, f64)) -> (usize, f64) {
+    if b.1 < a.1 || b.1 == a.1 && b.0 < a.0 {
+        b
+    } else {
+        a
+    }
+}
@@ -0,0 +1,327 @@
+//! Delaunay triangulation and Voronoi diagram on the sphere
+//!
+//! Points are given as longitude/latitude pairs in degrees. The spherical
+//! Delaunay triangulation is the convex hull of the points placed on the
+//! unit sphere, which is computed with the stereographic projection trick:
+//!
+//! 1. the first point is taken as the projection pole and every other point
+//!    is projected stereographically onto the plane, which maps circles on
+//!    the sphere to circles (or lines) in the plane;
+//! 2. the projected points are triangulated with [`Delaunator`], giving all
+//!    the triangles that do not touch the pole;
+//! 3. each convex hull edge of the planar triangulation is closed with a
+//!    triangle to the pole.
+//!
+//! The result covers the whole sphere, so it has no hull and every halfedge
+//! has a twin.
+
+use alloc::{vec, vec::Vec};
+
+use num_traits::Float;
+#[cfg(feature = "wasm")]
+use wasm_bindgen::prelude::*;
+
+use crate::{next_halfedge, Delaunator, Error};
+
+/// Delaunay triangulation of longitude/latitude points on the sphere
+///
+/// Triangles wind like the planar ones when the sphere is seen from
+/// outside with north up (clockwise).
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+#[derive(Debug)]
+pub struct SphericalDelaunay {
+    // Input coordinates [lon0, lat0, lon1, lat1, ...] in degrees
+    pub(crate) points: Vec<f64>,
+
+    #[cfg_attr(feature = "wasm", wasm_bindgen(skip))]
+    pub triangles: Vec<u32>,
+    #[cfg_attr(feature = "wasm", wasm_bindgen(skip))]
+    pub halfedges: Vec<i32>,
+
+    // Points as unit vectors [x0, y0, z0, x1, y1, z1, ...]
+    vectors: Vec<f64>,
+}
+
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+impl SphericalDelaunay {
+    /// Triangulates a flat array of point coordinates on the sphere
+    ///
+    /// The input format is `[lon0, lat0, lon1, lat1, ...]` in degrees.
+    /// Returns error if the input is invalid.
+    #[cfg_attr(feature = "wasm", wasm_bindgen(constructor))]
+    pub fn new(points: Vec<f64>) -> Result<SphericalDelaunay, Error> {
+        if points.is_empty() || !points.len().is_multiple_of(2) {
+            return Err(Error::InvalidCoordinates);
+        }
+
+        if points.iter().any(|v| !v.is_finite()) {
+            return Err(Error::NonFiniteCoordinate);
+        }
+
+        let vectors: Vec<f64> = points
+            .chunks_exact(2)
+            .flat_map(|p| to_vector(p[0], p[1]))
+            .collect();
+
+        let mut sphere = SphericalDelaunay {
+            points,
+            triangles: Vec::new(),
+            halfedges: Vec::new(),
+            vectors,
+        };
+        sphere.triangulate()?;
+
+        Ok(sphere)
+    }
+
+    /// Computes the circumcenter of every triangle
+    ///
+    /// Returns `[lon0, lat0, lon1, lat1, ...]` in degrees, one pair per
+    /// triangle. These are the vertices of the spherical Voronoi diagram.
+    #[cfg_attr(feature = "wasm", wasm_bindgen(js_name = "circumcenters"))]
+    pub fn circumcenters(&self) -> Vec<f64> {
+        self.triangles
+            .chunks_exact(3)
+            .flat_map(|t| {
+                let [x, y, z] = self.circumcenter_vector(t[0], t[1], t[2]);
+                to_lon_lat(x, y, z)
+            })
+            .collect()
+    }
+}
+
+// JavaScript API methods for web use
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+impl SphericalDelaunay {
+    /// Get triangulation result as array of indices
+    ///
+    /// Returns a Uint32Array containing indices that form triangles
+    /// (each group of 3 values represents a triangle)
+    #[wasm_bindgen(getter, js_name = "triangles")]
+    pub fn get_triangles(&self) -> js_sys::Uint32Array {
+        let array = js_sys::Uint32Array::new_with_length(self.triangles.len() as u32);
+        array.copy_from(&self.triangles);
+        array
+    }
+
+    /// Get halfedges result as array
+    ///
+    /// Returns an Int32Array containing halfedge indices
+    /// that allow traversal of the triangulation
+    #[wasm_bindgen(getter, js_name = "halfedges")]
+    pub fn get_halfedges(&self) -> js_sys::Int32Array {
+        let array = js_sys::Int32Array::new_with_length(self.halfedges.len() as u32);
+        array.copy_from(&self.halfedges);
+        array
+    }
+
+    /// Get input coordinates as array
+    ///
+    /// Returns a Float64Array containing the input coordinates
+    /// in the format [lon0, lat0, lon1, lat1, ...]
+    #[wasm_bindgen(getter, js_name = "points")]
+    pub fn get_points(&self) -> js_sys::Float64Array {
+        let array = js_sys::Float64Array::new_with_length(self.points.len() as u32);
+        array.copy_from(&self.points);
+        array
+    }
+}
+
+impl SphericalDelaunay {
+    /// Computes the spherical Voronoi cell of every point
+    ///
+    /// Each cell is a closed polygon `[lon0, lat0, lon1, lat1, ...]` in
+    /// degrees whose vertices are circumcenters, wound counterclockwise
+    /// when seen from outside the sphere as GeoJSON expects.
+    /// Points left out of the triangulation (duplicates) get an empty cell.
+    pub fn voronoi_cells(&self) -> Vec<Vec<f64>> {
+        let centers = self.circumcenters();
+        let mut inedges = vec![-1i32; self.points.len() / 2];
+        for e in 0..self.halfedges.len() {
+            inedges[self.triangles[next_halfedge(e)] as usize] = e as i32;
+        }
+
+        inedges
+            .iter()
+            .map(|&e0| {
+                let mut cell = Vec::new();
+                if e0 == -1 {
+                    return cell;
+                }
+
+                // Turn around the point through the triangles touching it
+                let mut e = e0 as usize;
+                loop {
+                    let t = e / 3;
+                    cell.extend_from_slice(&centers[2 * t..2 * t + 2]);
+                    e = self.halfedges[next_halfedge(e)] as usize;
+                    if e == e0 as usize {
+                        break;
+                    }
+                }
+                cell
+            })
+            .collect()
+    }
+
+    // Builds the triangulation with the stereographic projection from the
+    // first point
+    fn triangulate(&mut self) -> Result<(), Error> {
+        let n = self.points.len() / 2;
+        let pole = [self.vectors[0], self.vectors[1], self.vectors[2]];
+
+        // Orthonormal basis of the projection plane, chosen so that planar
+        // triangles keep their winding once mapped back to the sphere
+        let [u, w] = plane_basis(pole);
+
+        let mut ids = Vec::with_capacity(n);
+        let mut projected = Vec::with_capacity(2 * n);
+        for i in 1..n {
+            let v = [
+                self.vectors[3 * i],
+                self.vectors[3 * i + 1],
+                self.vectors[3 * i + 2],
+            ];
+            let s = 1.0 / (1.0 - dot(v, pole));
+            let (x, y) = (dot(v, u) * s, dot(v, w) * s);
+
+            // Points at the pole project to infinity
+            if x.is_finite() && y.is_finite() {
+                ids.push(i as u32);
+                projected.push(x);
+                projected.push(y);
+            }
+        }
+
+        if ids.len() == 2 {
+            self.triangulate_triple(ids[0], ids[1]);
+            return Ok(());
+        }
+        if ids.len() < 3 {
+            return Ok(());
+        }
+
+        let planar = Delaunator::new(projected)?;
+        if planar.triangles.is_empty() {
+            // All points lie on a single circle through the pole
+            return Ok(());
+        }
+
+        let mut triangles: Vec<u32> = planar.triangles.iter().map(|&i| ids[i as usize]).collect();
+        let mut halfedges = planar.halfedges;
+
+        // Close every hull edge a -> b with the triangle (b, a, pole), and
+        // remember the halfedges to and from the pole around each hull point
+        let hull_edges: Vec<usize> = (0..halfedges.len())
+            .filter(|&e| halfedges[e] == -1)
+            .collect();
+        let mut to_pole = vec![0usize; n];
+        let mut from_pole = vec![0usize; n];
+        for &e in &hull_edges {
+            let a = triangles[e];
+            let b = triangles[next_halfedge(e)];
+            let t = triangles.len();
+
+            triangles.extend_from_slice(&[b, a, 0]);
+            halfedges.extend_from_slice(&[e as i32, -1, -1]);
+            halfedges[e] = t as i32;
+
+            to_pole[a as usize] = t + 1;
+            from_pole[b as usize] = t + 2;
+        }
+
+        for &e in &hull_edges {
+            let i = triangles[e] as usize;
+            halfedges[to_pole[i]] = from_pole[i] as i32;
+            halfedges[from_pole[i]] = to_pole[i] as i32;
+        }
+
+        self.triangles = triangles;
+        self.halfedges = halfedges;
+        Ok(())
+    }
+
+    // Covers the sphere with the two sides of the triangle through the pole
+    // and two other points, unless the three lie on a great circle
+    fn triangulate_triple(&mut self, a: u32, b: u32) {
+        let det = dot(cross(self.vector(0), self.vector(a)), self.vector(b));
+        if det == 0.0 {
+            return;
+        }
+
+        // Both triangles wind clockwise seen from outside, the second one
+        // covering the rest of the sphere
+        let (a, b) = if det < 0.0 { (a, b) } else { (b, a) };
+        self.triangles = vec![0, a, b, 0, b, a];
+        self.halfedges = vec![5, 4, 3, 2, 1, 0];
+    }
+
+    fn vector(&self, i: u32) -> [f64; 3] {
+        let i = i as usize;
+        [
+            self.vectors[3 * i],
+            self.vectors[3 * i + 1],
+            self.vectors[3 * i + 2],
+        ]
+    }
+
+    // Center of the circle through three points on the sphere, on the side
+    // where the triangle winds clockwise
+    fn circumcenter_vector(&self, a: u32, b: u32, c: u32) -> [f64; 3] {
+        let (a, b, c) = (self.vector(a), self.vector(b), self.vector(c));
+        let n = cross(sub(c, a), sub(b, a));
+        let len = Float::sqrt(dot(n, n));
+        [n[0] / len, n[1] / len, n[2] / len]
+    }
+}
+
+// Converts longitude/latitude in degrees to a point on the unit sphere
+fn to_vector(lon: f64, lat: f64) -> [f64; 3] {
+    let (lon, lat) = (lon.to_radians(), lat.to_radians());
+    [
+        Float::cos(lat) * Float::cos(lon),
+        Float::cos(lat) * Float::sin(lon),
+        Float::sin(lat),
+    ]
+}
+
+// Converts a point on the unit sphere to longitude/latitude in degrees
+fn to_lon_lat(x: f64, y: f64, z: f64) -> [f64; 2] {
+    [
+        Float::atan2(y, x).to_degrees(),
+        Float::asin(z.clamp(-1.0, 1.0)).to_degrees(),
+    ]
+}
+
+// Two unit vectors orthogonal to the pole and to each other, such that
+// (u, w, pole) is a left-handed frame
+fn plane_basis(pole: [f64; 3]) -> [[f64; 3]; 2] {
+    // Start from the axis least aligned with the pole for stability
+    let axis = if pole[0].abs() < 0.5 {
+        [1.0, 0.0, 0.0]
+    } else {
+        [0.0, 1.0, 0.0]
+    };
+    let u = cross(pole, axis);
+    let len = Float::sqrt(dot(u, u));
+    let u = [u[0] / len, u[1] / len, u[2] / len];
+    let w = cross(u, pole);
+    [u, w]
+}
+
+fn dot(a: [f64; 3], b: [f64; 3]) -> f64 {
+    a[0] * b[0] + a[1] * b[1] + a[2] * b[2]
+}
+
+fn cross(a: [f64; 3], b: [f64; 3]) -> [f64; 3] {
+    [
+        a[1] * b[2] - a[2] * b[1],
+        a[2] * b[0] - a[0] * b[2],
+        a[0] * b[1] - a[1] * b[0],
+    ]
+}
+
+fn sub(a: [f64; 3], b: [f64; 3]) -> [f64; 3] {
+    [a[0] - b[0], a[1] - b[1], a[2] - b[2]]
+}
@@ -0,0 +1,788 @@
+//! Streaming triangulation of point sets larger than memory
+//!
+//! [`Delaunator`](crate::Delaunator) holds every point and every triangle
+//! at once. The streaming triangulation of Isenburg et al. instead reads
+//! the points one by one and writes each triangle to a [`Sink`] as soon as
+//! it is final, then forgets it, so that only the front between the points
+//! read and the points to come stays in memory.
+//!
+//! What makes a triangle final is spatial finalization. The bounding box of
+//! the points is divided into [`Cells`], and the stream tells when a cell
+//! has received all of its points. A triangle whose circumcircle only
+//! overlaps finished cells can no longer be destroyed by a new point, so it
+//! belongs to the Delaunay triangulation of the whole set. Points are
+//! forgotten in turn once all of their triangles have been written.
+//!
+//! [`triangulate`] finalizes any source of points that can be read three
+//! times, such as a file: once for the bounding box, once to count the
+//! points of every cell and once to insert them, finishing each cell with
+//! its last point. Memory then depends on the spatial coherence of the
+//! order of the points, not on their number: points sorted along rows or a
+//! space-filling curve keep a narrow front, while a random order keeps
+//! most of the triangulation until the end. [`Stream`] takes the points
+//! and finalization events directly, for sources that already know when
+//! their cells are complete.
+//!
+//! Points are inserted incrementally with the Bowyer-Watson algorithm, as
+//! in the [`regular`](crate::regular) module. Triangles are written with
+//! the corners numbered in stream order, counterclockwise as in
+//! `Delaunator::triangles`; duplicate points are left out. The convex hull
+//! stays in memory until [`Stream::finish`].
+
+use alloc::{collections::VecDeque, vec, vec::Vec};
+
+use num_traits::Float;
+use robust::{incircle, Coord};
+
+use crate::{circumcenter, dist, next_halfedge, orient2d, spread_fits, Error};
+
+// Virtual point at infinity, the third corner of ghost triangles
+const INFINITE: u32 = u32::MAX;
+// Twin of the halfedges whose triangle across has been written out
+const DEAD: usize = usize::MAX;
+// Average number of points per cell chosen by `triangulate`, and the
+// largest number of cells
+const CELL_POINTS: usize = 16;
+const MAX_CELLS: usize = 1 << 20;
+
+/// Destination of the final triangles of a stream
+pub trait Sink {
+    /// Receives a triangle as the stream indices of its corners
+    fn triangle(&mut self, corners: [usize; 3]);
+}
+
+/// Collects the triangles into a flat array, as `Delaunator::triangles`
+impl Sink for Vec<usize> {
+    fn triangle(&mut self, corners: [usize; 3]) {
+        self.extend_from_slice(&corners);
+    }
+}
+
+impl<F: FnMut([usize; 3])> Sink for F {
+    fn triangle(&mut self, corners: [usize; 3]) {
+        self(corners)
+    }
+}
+
+/// Regular grid of cells over the bounding box of a stream
+#[derive(Debug, Clone, PartialEq)]
+pub struct Cells {
+    bounds: [f64; 4],
+    cols: usize,
+    rows: usize,
+}
+
+impl Cells {
+    /// Divides `bounds`, given as `[min_x, min_y, max_x, max_y]`, into
+    /// `cols` by `rows` cells
+    ///
+    /// Returns error if the bounds are not finite and ordered or if there
+    /// are no cells, and if the predicates cannot be evaluated over points
+    /// spread across the bounds.
+    pub fn new(bounds: [f64; 4], cols: usize, rows: usize) -> Result<Cells, Error> {
+        let [min_x, min_y, max_x, max_y] = bounds;
+        if bounds.iter().any(|v| !v.is_finite()) || min_x > max_x || min_y > max_y {
+            return Err(Error::InvalidDomain);
+        }
+        if cols == 0 || rows == 0 {
+            return Err(Error::InvalidDomain);
+        }
+        if !spread_fits((max_x - min_x).max(max_y - min_y)) {
+            return Err(Error::CoordinateRange);
+        }
+        Ok(Cells { bounds, cols, rows })
+    }
+
+    /// Number of cells, numbered row by row from `min_y`
+    pub fn count(&self) -> usize {
+        self.cols * self.rows
+    }
+
+    /// Cell containing the point, or `None` outside of the bounds
+    pub fn cell(&self, x: f64, y: f64) -> Option<usize> {
+        let [min_x, min_y, max_x, max_y] = self.bounds;
+        if !(min_x <= x && x <= max_x && min_y <= y && y <= max_y) {
+            return None;
+        }
+        Some(self.row(y) * self.cols + self.col(x))
+    }
+
+    // Column of abscissa x, clamped to the grid
+    fn col(&self, x: f64) -> usize {
+        let width = self.bounds[2] - self.bounds[0];
+        if width == 0.0 {
+            return 0;
+        }
+        let col = Float::floor((x - self.bounds[0]) / width * self.cols as f64);
+        (col as usize).min(self.cols - 1)
+    }
+
+    // Row of ordinate y, clamped to the grid
+    fn row(&self, y: f64) -> usize {
+        let height = self.bounds[3] - self.bounds[1];
+        if height == 0.0 {
+            return 0;
+        }
+        let row = Float::floor((y - self.bounds[1]) / height * self.rows as f64);
+        (row as usize).min(self.rows - 1)
+    }
+}
+
+/// Triangulates the points of a source that can be read several times
+///
+/// `points` is called three times and must yield the same points in the
+/// same order each time. The triangles are written to `sink` as soon as
+/// they are final, which is then returned. Returns error if a coordinate
+/// is not finite, if the points are too spread out for the predicates, or
+/// if the passes yield different points.
+pub fn triangulate<F, P, S>(mut points: F, sink: S) -> Result<S, Error>
+where
+    F: FnMut() -> P,
+    P: IntoIterator<Item = (f64, f64)>,
+    S: Sink,
+{
+    // First pass: bounding box
+    let mut bounds = [
+        f64::INFINITY,
+        f64::INFINITY,
+        f64::NEG_INFINITY,
+        f64::NEG_INFINITY,
+    ];
+    let mut n = 0;
+    for (x, y) in points() {
+        if !x.is_finite() || !y.is_finite() {
+            return Err(Error::NonFiniteCoordinate);
+        }
+        bounds = [
+            bounds[0].min(x),
+            bounds[1].min(y),
+            bounds[2].max(x),
+            bounds[3].max(y),
+        ];
+        n += 1;
+    }
+    if n == 0 {
+        return Ok(sink);
+    }
+
+    // Cells of about CELL_POINTS points, as square as the bounds allow
+    let count = (n / CELL_POINTS).clamp(1, MAX_CELLS);
+    let width = bounds[2] - bounds[0];
+    let height = bounds[3] - bounds[1];
+    let cols = if width == 0.0 {
+        1
+    } else if height == 0.0 {
+        count
+    } else {
+        (Float::ceil(Float::sqrt(count as f64 * width / height)) as usize).clamp(1, count)
+    };
+    let rows = count.div_ceil(cols);
+    let cells = Cells::new(bounds, cols, rows)?;
+
+    // Second pass: points per cell
+    let mut remaining = vec![0usize; cells.count()];
+    for (x, y) in points() {
+        let c = cells.cell(x, y).ok_or(Error::InvalidCoordinates)?;
+        remaining[c] += 1;
+    }
+
+    // Third pass: insertion, finishing each cell with its last point
+    let mut stream = Stream::new(cells, sink);
+    for (c, &count) in remaining.iter().enumerate() {
+        if count == 0 {
+            stream.finalize(c);
+        }
+    }
+    for (x, y) in points() {
+        let c = stream.cells.cell(x, y).ok_or(Error::InvalidCoordinates)?;
+        stream.insert(x, y)?;
+        remaining[c] -= 1;
+        if remaining[c] == 0 {
+            stream.finalize(c);
+        }
+    }
+
+    Ok(stream.finish())
+}
+
+/// Streaming Delaunay triangulation over a grid of cells
+///
+/// Points are numbered in the order they are inserted. Once a cell is
+/// finalized, no point may be inserted in it anymore.
+#[derive(Debug)]
+pub struct Stream<S> {
+    cells: Cells,
+    finalized: Vec<bool>,
+    // Triangles waiting for each cell to be finalized, with the generation
+    // of their slot to tell them from the triangles that replaced them, and
+    // the number of the cell in their circumcircle's box
+    waiting: Vec<Vec<(usize, u32, usize)>>,
+    // Per cell, the slot of the last point inserted in it, from which the
+    // location of the next one starts
+    hints: Vec<Option<u32>>,
+    sink: S,
+    mesh: Mesh,
+    len: usize,
+}
+
+impl<S: Sink> Stream<S> {
+    /// Starts an empty stream writing its triangles to `sink`
+    pub fn new(cells: Cells, sink: S) -> Stream<S> {
+        let len = cells.count();
+        Stream {
+            cells,
+            finalized: vec![false; len],
+            waiting: vec![Vec::new(); len],
+            hints: vec![None; len],
+            sink,
+            mesh: Mesh::default(),
+            len: 0,
+        }
+    }
+
+    /// Inserts the next point and returns its index in the stream
+    ///
+    /// Returns error if a coordinate is not finite, or if the point lies
+    /// outside of the cells or in a finalized cell.
+    pub fn insert(&mut self, x: f64, y: f64) -> Result<usize, Error> {
+        if !x.is_finite() || !y.is_finite() {
+            return Err(Error::NonFiniteCoordinate);
+        }
+        let c = match self.cells.cell(x, y) {
+            Some(c) if !self.finalized[c] => c,
+            _ => return Err(Error::InvalidCoordinates),
+        };
+
+        // The triangles around a point of an open cell are never written
+        // out, so the walk from there rarely runs into a written out one
+        if let Some(q) = self.hints[c] {
+            self.mesh.last = self.mesh.incident[q as usize];
+        }
+
+        let i = self.len;
+        self.len += 1;
+        let started = self.mesh.started;
+        let p = self.mesh.add(i, x, y);
+        if self.mesh.refs[p as usize] > 0 {
+            self.hints[c] = Some(p);
+        }
+
+        // The new triangles pass through the new point, so they wait at
+        // least for its cell, except those made from the points held back
+        // until the first triangle
+        let fresh = core::mem::take(&mut self.mesh.fresh);
+        for &(t, generation) in &fresh {
+            if !self.mesh.is_current(t, generation) {
+                continue;
+            }
+            if started {
+                self.waiting[c].push((t, generation, 0));
+            } else {
+                self.wait(t, 0);
+            }
+        }
+        self.mesh.fresh = fresh;
+        self.mesh.fresh.clear();
+        Ok(i)
+    }
+
+    /// Marks cell `c` as complete, writing out the triangles that became
+    /// final
+    pub fn finalize(&mut self, c: usize) {
+        if self.finalized[c] {
+            return;
+        }
+        self.finalized[c] = true;
+        self.hints[c] = None;
+
+        for (t, generation, skip) in core::mem::take(&mut self.waiting[c]) {
+            if self.mesh.is_current(t, generation) {
+                self.wait(t, skip);
+            }
+        }
+    }
+
+    /// Writes out the remaining triangles and returns the sink
+    pub fn finish(mut self) -> S {
+        for t in 0..self.mesh.alive.len() {
+            if self.mesh.alive[t] && !self.mesh.is_ghost(t) {
+                let corners = self.mesh.release(t);
+                self.sink.triangle(corners);
+            }
+        }
+        self.sink
+    }
+
+    /// Cells of the stream
+    pub fn cells(&self) -> &Cells {
+        &self.cells
+    }
+
+    /// Number of points held in memory
+    pub fn resident_points(&self) -> usize {
+        self.mesh.ids.len() - self.mesh.free_points.len()
+    }
+
+    /// Number of triangles held in memory, counting those joining the hull
+    /// to the point at infinity
+    pub fn resident_triangles(&self) -> usize {
+        self.mesh.alive.len() - self.mesh.free.len()
+    }
+
+    // Writes out triangle t if no point to come can fall in its
+    // circumcircle, otherwise makes it wait for a cell that one could. The
+    // cells under the circumcircle before `skip` are known to be finalized.
+    fn wait(&mut self, t: usize, skip: usize) {
+        match self.open_cell(t, skip) {
+            Some((c, skip)) => self.waiting[c].push((t, self.mesh.generations[t], skip)),
+            None => {
+                let corners = self.mesh.release(t);
+                self.sink.triangle(corners);
+            }
+        }
+    }
+
+    // First cell that is not finalized under the bounding box of the
+    // circumcircle of finite triangle t, from the cell numbered `skip` in
+    // the box, with its number. Cells are only ever finalized, so later
+    // calls can skip the cells before it.
+    fn open_cell(&self, t: usize, skip: usize) -> Option<(usize, usize)> {
+        let [a, b, c] = [0, 1, 2].map(|k| self.mesh.point(self.mesh.triangles[3 * t + k]));
+        let (x, y) = circumcenter(a.0, a.1, b.0, b.1, c.0, c.1);
+
+        // Widened to cover the rounding of the circumcenter, which grows
+        // with the condition number of the triangle
+        let (dx, dy, ex, ey) = (b.0 - a.0, b.1 - a.1, c.0 - a.0, c.1 - a.1);
+        let condition = ((dx * ey).abs() + (dy * ex).abs()) / (dx * ey - dy * ex).abs();
+        let r = Float::sqrt(dist(x, y, a.0, a.1));
+        let r = r + 64.0 * f64::EPSILON * (condition * r + x.abs() + y.abs());
+
+        let cells = &self.cells;
+        let (col, row, cols, rows) = if r.is_finite() {
+            let (col, row) = (cells.col(x - r), cells.row(y - r));
+            (
+                col,
+                row,
+                cells.col(x + r) - col + 1,
+                cells.row(y + r) - row + 1,
+            )
+        } else {
+            (0, 0, cells.cols, cells.rows)
+        };
+
+        (skip..cols * rows)
+            .map(|k| (k, (row + k / cols) * cells.cols + col + k % cols))
+            .find(|&(_, c)| !self.finalized[c])
+            .map(|(k, c)| (c, k))
+    }
+}
+
+// Triangulation of the points in memory. As in `regular::Mesh`, ghost
+// triangles cover the outside of the hull and freed triangle slots are
+// reused, and so are the slots of the points no triangle uses anymore.
+#[derive(Debug, Default)]
+struct Mesh {
+    // Point slots
+    coords: Vec<f64>,
+    ids: Vec<usize>,
+    refs: Vec<u32>,
+    // A triangle around each point
+    incident: Vec<usize>,
+    free_points: Vec<u32>,
+
+    // Triangle slots
+    triangles: Vec<u32>,
+    halfedges: Vec<usize>,
+    alive: Vec<bool>,
+    generations: Vec<u32>,
+    free: Vec<usize>,
+
+    // Points read before the first three non-collinear ones, and the index
+    // in `pending` of the first one distinct from the first
+    pending: Vec<u32>,
+    second: Option<usize>,
+    started: bool,
+
+    // Triangles created since the stream last checked them
+    fresh: Vec<(usize, u32)>,
+
+    // Triangle the next point location starts from
+    last: usize,
+    // Rotates the edge tested first by the walk, which can otherwise cycle
+    walk_turn: usize,
+
+    // Per triangle, the last insertion that tested it, times two, plus one
+    // when it conflicted with the inserted point
+    marks: Vec<u32>,
+    stamp: u32,
+
+    // Scratch buffers reused by every insertion
+    cavity: Vec<usize>,
+    stack: Vec<usize>,
+    boundary: Vec<(u32, u32, usize)>,
+    created: Vec<usize>,
+    dropped: Vec<u32>,
+    queue: VecDeque<usize>,
+}
+
+impl Mesh {
+    fn point(&self, p: u32) -> (f64, f64) {
+        let p = p as usize;
+        (self.coords[2 * p], self.coords[2 * p + 1])
+    }
+
+    fn is_ghost(&self, t: usize) -> bool {
+        self.triangles[3 * t..3 * t + 3].contains(&INFINITE)
+    }
+
+    fn is_current(&self, t: usize, generation: u32) -> bool {
+        self.alive[t] && self.generations[t] == generation
+    }
+
+    // Stores point i of the stream and adds it to the triangulation, or
+    // keeps it for later while all the points so far are collinear
+    fn add(&mut self, i: usize, x: f64, y: f64) -> u32 {
+        let p = match self.free_points.pop() {
+            Some(p) => {
+                let k = p as usize;
+                self.coords[2 * k] = x;
+                self.coords[2 * k + 1] = y;
+                self.ids[k] = i;
+                p
+            }
+            None => {
+                self.coords.extend_from_slice(&[x, y]);
+                self.ids.push(i);
+                self.refs.push(0);
+                self.incident.push(0);
+                (self.ids.len() - 1) as u32
+            }
+        };
+
+        if self.started {
+            self.insert(p);
+            return p;
+        }
+
+        self.pending.push(p);
+        let a = self.pending[0];
+        let Some(second) = self.second else {
+            if self.point(p) != self.point(a) {
+                self.second = Some(self.pending.len() - 1);
+            }
+            return p;
+        };
+        let b = self.pending[second];
+        if orient(self.point(a), self.point(b), self.point(p)) == 0.0 {
+            return p;
+        }
+
+        self.start([a, b, p]);
+        let pending = core::mem::take(&mut self.pending);
+        for &q in &pending {
+            if q != a && q != b && q != p {
+                self.insert(q);
+            }
+        }
+        p
+    }
+
+    // Starts from the triangle of three non-collinear points and the three
+    // ghost triangles around it, laid out as in `regular::Mesh::new`
+    fn start(&mut self, seeds: [u32; 3]) {
+        let [a, mut b, mut c] = seeds;
+        if orient(self.point(a), self.point(b), self.point(c)) < 0.0 {
+            core::mem::swap(&mut b, &mut c);
+        }
+        self.started = true;
+
+        let t = self.create([a, b, c]);
+        self.halfedges[3 * t..3 * t + 3].copy_from_slice(&[3, 6, 9]);
+        for k in 0..3 {
+            let u = self.triangles[k];
+            let v = self.triangles[(k + 1) % 3];
+            let prev = 3 * ((k + 2) % 3 + 1);
+            let g = self.create([v, u, INFINITE]);
+            self.halfedges[3 * g..3 * g + 3].copy_from_slice(&[
+                k,
+                prev + 2,
+                3 * ((k + 1) % 3 + 1) + 1,
+            ]);
+        }
+        self.last = t;
+        self.fresh.push((t, self.generations[t]));
+    }
+
+    // Fills a free triangle slot, leaving its halfedges to the caller
+    fn create(&mut self, corners: [u32; 3]) -> usize {
+        let t = match self.free.pop() {
+            Some(t) => t,
+            None => {
+                self.triangles.extend_from_slice(&[0; 3]);
+                self.halfedges.extend_from_slice(&[0; 3]);
+                self.alive.push(false);
+                self.generations.push(0);
+                self.marks.push(0);
+                self.alive.len() - 1
+            }
+        };
+
+        self.triangles[3 * t..3 * t + 3].copy_from_slice(&corners);
+        self.alive[t] = true;
+        self.generations[t] = self.generations[t].wrapping_add(1);
+        for p in corners {
+            if p != INFINITE {
+                self.refs[p as usize] += 1;
+                self.incident[p as usize] = t;
+            }
+        }
+        t
+    }
+
+    // Drops a reference to point p, freeing its slot with the last one
+    fn unref(&mut self, p: u32) {
+        if p == INFINITE {
+            return;
+        }
+        self.refs[p as usize] -= 1;
+        if self.refs[p as usize] == 0 {
+            self.free_points.push(p);
+        }
+    }
+
+    // Writes out triangle t: frees its slot, cuts it from its neighbors and
+    // returns its corners as stream indices
+    fn release(&mut self, t: usize) -> [usize; 3] {
+        let corners = [0, 1, 2].map(|k| self.triangles[3 * t + k]);
+        for e in 3 * t..3 * t + 3 {
+            let twin = self.halfedges[e];
+            if twin != DEAD {
+                self.halfedges[twin] = DEAD;
+                // Keep the next walk starting from a live triangle
+                if t == self.last {
+                    self.last = twin / 3;
+                }
+            }
+        }
+        self.alive[t] = false;
+        self.free.push(t);
+
+        let ids = corners.map(|p| self.ids[p as usize]);
+        for p in corners {
+            self.unref(p);
+        }
+        ids
+    }
+
+    fn insert(&mut self, i: u32) {
+        let Some(t) = self.locate(i).filter(|&t| self.conflicts(t, i)) else {
+            // Duplicate of a point in the triangulation
+            self.free_points.push(i);
+            return;
+        };
+
+        // Grow the cavity of triangles in conflict with the new point and
+        // collect the halfedges on its boundary
+        self.stamp += 1;
+        let (tested, conflicted) = (2 * self.stamp, 2 * self.stamp + 1);
+        self.cavity.clear();
+        self.boundary.clear();
+        self.marks[t] = conflicted;
+        self.cavity.push(t);
+        self.stack.push(t);
+
+        while let Some(t) = self.stack.pop() {
+            for e in 3 * t..3 * t + 3 {
+                let twin = self.halfedges[e];
+                // Written out triangles never conflict
+                let inside = if twin == DEAD {
+                    false
+                } else {
+                    let o = twin / 3;
+                    let mark = self.marks[o];
+                    if mark == conflicted {
+                        true
+                    } else if mark == tested {
+                        false
+                    } else if self.conflicts(o, i) {
+                        self.marks[o] = conflicted;
+                        self.cavity.push(o);
+                        self.stack.push(o);
+                        true
+                    } else {
+                        self.marks[o] = tested;
+                        false
+                    }
+                };
+
+                if !inside {
+                    let u = self.triangles[e];
+                    let v = self.triangles[next_halfedge(e)];
+                    self.boundary.push((u, v, twin));
+                }
+            }
+        }
+
+        // Remember the corners of the cavity, released once the new
+        // triangles hold their own references
+        self.dropped.clear();
+        for k in 0..self.cavity.len() {
+            let t = self.cavity[k];
+            self.dropped
+                .extend_from_slice(&self.triangles[3 * t..3 * t + 3]);
+            self.alive[t] = false;
+        }
+        self.free.extend_from_slice(&self.cavity);
+
+        // Join every boundary edge to the new point. The cavity is star
+        // shaped around it, so the new triangles fan around the point and the
+        // one starting at v follows the one ending at v.
+        self.created.clear();
+        for k in 0..self.boundary.len() {
+            let (u, v, outside) = self.boundary[k];
+            let t = self.create([u, v, i]);
+            self.halfedges[3 * t] = outside;
+            if outside != DEAD {
+                self.halfedges[outside] = 3 * t;
+            }
+            self.created.push(t);
+        }
+        for k in 0..self.created.len() {
+            let t = self.created[k];
+            let v = self.boundary[k].1;
+            let next = self.boundary.iter().position(|b| b.0 == v).unwrap();
+            let s = self.created[next];
+            self.halfedges[3 * t + 1] = 3 * s + 2;
+            self.halfedges[3 * s + 2] = 3 * t + 1;
+        }
+
+        for k in 0..self.dropped.len() {
+            self.unref(self.dropped[k]);
+        }
+
+        for k in 0..self.created.len() {
+            let t = self.created[k];
+            if !self.is_ghost(t) {
+                self.fresh.push((t, self.generations[t]));
+            }
+        }
+        self.last = self
+            .created
+            .iter()
+            .copied()
+            .find(|&t| !self.is_ghost(t))
+            .unwrap_or(self.created[0]);
+    }
+
+    // Finds the triangle containing point i, or a ghost triangle whose hull
+    // edge has i strictly outside, by walking across the edges i lies
+    // beyond. Falls back to a search of every triangle when the walk runs
+    // into triangles already written out.
+    fn locate(&mut self, i: u32) -> Option<usize> {
+        let p = self.point(i);
+        let mut t = self.last;
+        if !self.alive[t] {
+            return self.search(i, t);
+        }
+        if self.is_ghost(t) {
+            let twin = self.halfedges[self.ghost_edge(t)];
+            if twin == DEAD {
+                return self.search(i, t);
+            }
+            t = twin / 3;
+        }
+
+        'walk: loop {
+            self.walk_turn = (self.walk_turn + 1) % 3;
+            let mut blocked = false;
+            for j in 0..3 {
+                let e = 3 * t + (self.walk_turn + j) % 3;
+                let a = self.point(self.triangles[e]);
+                let b = self.point(self.triangles[next_halfedge(e)]);
+                if orient(a, b, p) < 0.0 {
+                    // Go around the triangles written out if possible
+                    let twin = self.halfedges[e];
+                    if twin == DEAD {
+                        blocked = true;
+                        continue;
+                    }
+                    t = twin / 3;
+                    if self.is_ghost(t) {
+                        return Some(t);
+                    }
+                    continue 'walk;
+                }
+            }
+            return if blocked { self.search(i, t) } else { Some(t) };
+        }
+    }
+
+    // Any triangle in conflict with point i, searched breadth first across
+    // the live triangles around t, then among all of them
+    fn search(&mut self, i: u32, t: usize) -> Option<usize> {
+        self.stamp += 1;
+        let visited = 2 * self.stamp;
+        self.queue.clear();
+        if self.alive[t] {
+            self.marks[t] = visited;
+            self.queue.push_back(t);
+        }
+
+        while let Some(t) = self.queue.pop_front() {
+            if self.conflicts(t, i) {
+                return Some(t);
+            }
+            for e in 3 * t..3 * t + 3 {
+                let twin = self.halfedges[e];
+                if twin != DEAD && self.marks[twin / 3] != visited {
+                    self.marks[twin / 3] = visited;
+                    self.queue.push_back(twin / 3);
+                }
+            }
+        }
+
+        (0..self.alive.len()).find(|&t| self.alive[t] && self.conflicts(t, i))
+    }
+
+    // Halfedge of the finite edge of ghost triangle t
+    fn ghost_edge(&self, t: usize) -> usize {
+        let k = (0..3)
+            .find(|&k| self.triangles[3 * t + k] == INFINITE)
+            .unwrap();
+        3 * t + (k + 1) % 3
+    }
+
+    // Whether inserting point i removes triangle t
+    fn conflicts(&self, t: usize, i: u32) -> bool {
+        if !self.is_ghost(t) {
+            // Exact, so that cocircular points never conflict
+            let [a, b, c] = [0, 1, 2].map(|k| self.triangles[3 * t + k]);
+            let [a, b, c, p] = [a, b, c, i].map(|k| {
+                let (x, y) = self.point(k);
+                Coord { x, y }
+            });
+            return incircle(a, b, c, p) < 0.0;
+        }
+
+        // A ghost conflicts with the points beyond its hull edge, as well as
+        // with points on the edge itself, endpoints included, when these
+        // remove the triangle inside the hull
+        let p = self.point(i);
+        let e = self.ghost_edge(t);
+        let a = self.point(self.triangles[e]);
+        let b = self.point(self.triangles[next_halfedge(e)]);
+        let side = orient(a, b, p);
+        if side != 0.0 {
+            return side > 0.0;
+        }
+
+        let twin = self.halfedges[e];
+        let along_a = (p.0 - a.0) * (b.0 - a.0) + (p.1 - a.1) * (b.1 - a.1);
+        let along_b = (p.0 - b.0) * (a.0 - b.0) + (p.1 - b.1) * (a.1 - b.1);
+        along_a >= 0.0 && along_b >= 0.0 && twin != DEAD && self.conflicts(twin / 3, i)
+    }
+}
+
+fn orient(a: (f64, f64), b: (f64, f64), c: (f64, f64)) -> f64 {
+    orient2d(a.0, a.1, b.0, b.1, c.0, c.1)
+}
@@ -0,0 +1,667 @@
+//! Sweep-hull triangulation
+//!
+//! The algorithm of the JavaScript delaunator: starting from the seed
+//! triangle with the smallest circumcircle near the center of the points,
+//! the points are inserted in order of distance from its circumcenter, so
+//! that each one lies outside of the hull of the previous ones. A new point
+//! is joined to every hull edge it sees, found through a hash of the hull by
+//! angle around the center, and the new triangles are flipped until they
+//! are Delaunay. The arrays are generic over their [`Index`] type.
+
+use alloc::{vec, vec::Vec};
+
+use cfg_if::cfg_if;
+use num_traits::Float;
+
+use crate::index::Index;
+use crate::scan::{self, Metric};
+use crate::{circumcenter, in_circle, orient2d, pseudo_angle, Sort};
+
+// Constant equivalent to JavaScript's EPSILON (2^-52)
+const EPSILON: f64 = 2.220446049250313e-16;
+// Fixed size stack for edge legalization
+const EDGE_STACK_SIZE: usize = 512;
+
+/// Arrays of the sweep, kept between runs to avoid allocating
+#[derive(Debug)]
+pub(crate) struct Sweep<I> {
+    // Outputs, with `I::NONE` for halfedges without opposite
+    pub(crate) triangles: Vec<I>,
+    pub(crate) halfedges: Vec<I>,
+    pub(crate) hull: Vec<I>,
+
+    triangles_len: usize,
+    hull_start: usize,
+    hash_size: usize,
+    hull_prev: Vec<I>,
+    hull_next: Vec<I>,
+    hull_tri: Vec<I>,
+    hull_hash: Vec<I>,
+    ids: Vec<I>,
+    dists: Vec<f64>,
+    cx: f64,
+    cy: f64,
+}
+
+impl<I: Index> Sweep<I> {
+    // Allocates the arrays used by the triangulation of n points
+    pub(crate) fn new(n: usize) -> Self {
+        let zero = I::from_usize(0);
+        let hash_size = Float::ceil(Float::sqrt(n as f64)) as usize; // Math.ceil(Math.sqrt(n))
+
+        Sweep {
+            triangles: Vec::new(),
+            halfedges: Vec::new(),
+            hull: Vec::new(),
+
+            triangles_len: 0,
+            hull_start: 0,
+            hash_size,
+            hull_prev: vec![zero; n],
+            hull_next: vec![zero; n],
+            hull_tri: vec![zero; n],
+            hull_hash: vec![I::NONE; hash_size],
+            ids: vec![zero; n],
+            dists: vec![0.0; n],
+            cx: 0.0,
+            cy: 0.0,
+        }
+    }
+
+    /// Triangulates the points into `triangles`, `halfedges` and `hull`,
+    /// reusing their allocations
+    pub(crate) fn run(&mut self, coords: &[f64], sort: Sort) {
+        let n = coords.len() >> 1;
+        self.triangles.clear();
+        self.halfedges.clear();
+        self.hull.clear();
+
+        // Bail if there are fewer than 3 points (not enough for triangulation)
+        if n < 3 {
+            self.hull.extend((0..n).map(I::from_usize));
+            return;
+        }
+
+        // Initialize point indices for sorting
+        for i in 0..n {
+            self.ids[i] = I::from_usize(i);
+        }
+
+        // Find the bounding box and a point close to the centroid
+        let (min_x, min_y, max_x, max_y) = scan::bounding_box(coords);
+
+        // Calculate centroid
+        let cx = (min_x + max_x) * 0.5;
+        let cy = (min_y + max_y) * 0.5;
+
+        // Find the point closest to the centroid
+        let (i0, _) = scan::argmin(
+            coords,
+            Metric::Dist {
+                x: cx,
+                y: cy,
+                skip_zero: false,
+            },
+        );
+
+        let i0x = coords[2 * i0];
+        let i0y = coords[2 * i0 + 1];
+
+        // Find the point closest to the first point
+        let (mut i1, _) = scan::argmin(
+            coords,
+            Metric::Dist {
+                x: i0x,
+                y: i0y,
+                skip_zero: true,
+            },
+        );
+
+        let mut i1x = coords[2 * i1];
+        let mut i1y = coords[2 * i1 + 1];
+
+        // Find the third point which forms the smallest circumcircle
+        let (mut i2, min_radius) = scan::argmin(
+            coords,
+            Metric::Circumradius {
+                i: i0,
+                j: i1,
+                a: (i0x, i0y),
+                b: (i1x, i1y),
+            },
+        );
+
+        let mut i2x = coords[2 * i2];
+        let mut i2y = coords[2 * i2 + 1];
+
+        // Handle collinear case (all points on a line)
+        if min_radius == f64::INFINITY {
+            // Order points by dx (or dy if all x are identical)
+            for i in 0..n {
+                self.dists[i] = coords[2 * i] - coords[0];
+                if self.dists[i] == 0.0 {
+                    self.dists[i] = coords[2 * i + 1] - coords[1];
+                }
+            }
+
+            sort_by_distance(&mut self.ids, &mut self.dists, sort);
+
+            let mut d0 = f64::NEG_INFINITY;
+            for &id in &self.ids {
+                let d = self.dists[id.to_usize()];
+                if d > d0 {
+                    self.hull.push(id);
+                    d0 = d;
+                }
+            }
+            return;
+        }
+
+        // Ensure counterclockwise orientation for the first three points
+        let orientation = orient2d(i0x, i0y, i1x, i1y, i2x, i2y);
+        if orientation < 0.0 {
+            // Swap the order of the second and third points
+            core::mem::swap(&mut i1, &mut i2);
+            core::mem::swap(&mut i1x, &mut i2x);
+            core::mem::swap(&mut i1y, &mut i2y);
+        }
+
+        // Calculate the circumcenter of the first triangle
+        let center = circumcenter(i0x, i0y, i1x, i1y, i2x, i2y);
+        self.cx = center.0;
+        self.cy = center.1;
+
+        // Sort the points by distance from the circumcenter
+        scan::distances(coords, center, &mut self.dists);
+        sort_by_distance(&mut self.ids, &mut self.dists, sort);
+
+        // Set up the initial triangle as the starting hull
+        self.hull_start = i0;
+        let mut hull_size = 3;
+
+        self.hull_next[i0] = I::from_usize(i1);
+        self.hull_prev[i2] = I::from_usize(i1);
+        self.hull_next[i1] = I::from_usize(i2);
+        self.hull_prev[i0] = I::from_usize(i2);
+        self.hull_next[i2] = I::from_usize(i0);
+        self.hull_prev[i1] = I::from_usize(i0);
+
+        self.hull_tri[i0] = I::from_usize(0);
+        self.hull_tri[i1] = I::from_usize(1);
+        self.hull_tri[i2] = I::from_usize(2);
+
+        // Fill hash with initial triangle edges
+        self.hull_hash.fill(I::NONE);
+
+        let key_i0 = self.hash_key(i0x, i0y);
+        let key_i1 = self.hash_key(i1x, i1y);
+        let key_i2 = self.hash_key(i2x, i2y);
+
+        self.hull_hash[key_i0] = I::from_usize(i0);
+        self.hull_hash[key_i1] = I::from_usize(i1);
+        self.hull_hash[key_i2] = I::from_usize(i2);
+
+        // Reset triangulation state, growing the arrays to the maximum
+        // possible number of triangles
+        self.triangles_len = 0;
+        let max_triangles = 2 * n - 5;
+        self.triangles.resize(max_triangles * 3, I::from_usize(0));
+        self.halfedges.resize(max_triangles * 3, I::NONE);
+
+        // Create the first triangle
+        self.add_triangle(i0, i1, i2, I::NONE, I::NONE, I::NONE);
+
+        // Process remaining points
+        let mut xp = 0.0;
+        let mut yp = 0.0;
+
+        for k in 0..n {
+            let i = self.ids[k].to_usize();
+            let x = coords[2 * i];
+            let y = coords[2 * i + 1];
+
+            // Skip near-duplicate points
+            if k > 0 && f64::abs(x - xp) <= EPSILON && f64::abs(y - yp) <= EPSILON {
+                continue;
+            }
+            xp = x;
+            yp = y;
+
+            // Skip seed triangle points
+            if i == i0 || i == i1 || i == i2 {
+                continue;
+            }
+
+            // Find a visible edge on the convex hull using edge hash
+            let mut start = I::NONE;
+            let key = self.hash_key(x, y);
+
+            for j in 0..self.hash_size {
+                start = self.hull_hash[(key + j) % self.hash_size];
+                if start != I::NONE && start != self.hull_next[start.to_usize()] {
+                    break;
+                }
+            }
+
+            let start = self.hull_prev[start.to_usize()].to_usize();
+            let mut e = start;
+            let mut q;
+            let mut visible = true;
+
+            // Find the visible edges on the convex hull
+            loop {
+                q = self.hull_next[e].to_usize();
+                if orient2d(
+                    x,
+                    y,
+                    coords[2 * e],
+                    coords[2 * e + 1],
+                    coords[2 * q],
+                    coords[2 * q + 1],
+                ) >= 0.0
+                {
+                    e = q;
+                    if e == start {
+                        visible = false;
+                        break;
+                    }
+                } else {
+                    break;
+                }
+            }
+
+            // Skip if no visible edges found
+            if !visible {
+                continue;
+            }
+
+            // Add the first triangle from the point
+            let mut t = self.add_triangle(
+                e,
+                i,
+                self.hull_next[e].to_usize(),
+                I::NONE,
+                I::NONE,
+                self.hull_tri[e],
+            );
+
+            // Recursively flip triangles from the point until they satisfy the Delaunay condition
+            self.hull_tri[i] = I::from_usize(self.legalize(coords, t + 2));
+            self.hull_tri[e] = I::from_usize(t);
+            hull_size += 1;
+
+            // Walk forward through the hull, adding more triangles and flipping recursively
+            let mut next = self.hull_next[e].to_usize();
+            loop {
+                q = self.hull_next[next].to_usize();
+                if orient2d(
+                    x,
+                    y,
+                    coords[2 * next],
+                    coords[2 * next + 1],
+                    coords[2 * q],
+                    coords[2 * q + 1],
+                ) < 0.0
+                {
+                    t = self.add_triangle(
+                        next,
+                        i,
+                        q,
+                        self.hull_tri[i],
+                        I::NONE,
+                        self.hull_tri[next],
+                    );
+                    self.hull_tri[i] = I::from_usize(self.legalize(coords, t + 2));
+                    self.hull_next[next] = I::from_usize(next); // mark as removed
+                    hull_size -= 1;
+                    next = q;
+                } else {
+                    break;
+                }
+            }
+
+            // Walk backward through the hull, adding more triangles and flipping
+            if e == start {
+                loop {
+                    q = self.hull_prev[e].to_usize();
+                    if orient2d(
+                        x,
+                        y,
+                        coords[2 * q],
+                        coords[2 * q + 1],
+                        coords[2 * e],
+                        coords[2 * e + 1],
+                    ) < 0.0
+                    {
+                        t = self.add_triangle(q, i, e, I::NONE, self.hull_tri[e], self.hull_tri[q]);
+                        self.legalize(coords, t + 2);
+                        self.hull_tri[q] = I::from_usize(t);
+                        self.hull_next[e] = I::from_usize(e); // mark as removed
+                        hull_size -= 1;
+                        e = q;
+                    } else {
+                        break;
+                    }
+                }
+            }
+
+            // Update hull indices
+            self.hull_start = e;
+            self.hull_prev[i] = I::from_usize(e);
+            self.hull_next[e] = I::from_usize(i);
+            self.hull_prev[next] = I::from_usize(i);
+            self.hull_next[i] = I::from_usize(next);
+
+            // Save the two new edges in the hash table
+            let key_xy = self.hash_key(x, y);
+            let key_e = self.hash_key(coords[2 * e], coords[2 * e + 1]);
+
+            self.hull_hash[key_xy] = I::from_usize(i);
+            self.hull_hash[key_e] = I::from_usize(e);
+        }
+
+        // Extract the hull as an array of point indices
+        self.hull.reserve(hull_size);
+        let mut e = self.hull_start;
+        for _ in 0..hull_size {
+            self.hull.push(I::from_usize(e));
+            e = self.hull_next[e].to_usize();
+        }
+
+        // Trim arrays to the actual number of triangles
+        self.triangles.truncate(self.triangles_len);
+        self.halfedges.truncate(self.triangles_len);
+    }
+
+    // Calculate a hash key for a point (used in finding visible edges on the hull)
+    fn hash_key(&self, x: f64, y: f64) -> usize {
+        let dx = x - self.cx;
+        let dy = y - self.cy;
+        let p = Float::floor(pseudo_angle(dx, dy) * self.hash_size as f64) as usize;
+        p % self.hash_size
+    }
+
+    // Add a triangle to the triangulation
+    fn add_triangle(&mut self, i0: usize, i1: usize, i2: usize, a: I, b: I, c: I) -> usize {
+        let t = self.triangles_len;
+
+        self.triangles[t] = I::from_usize(i0);
+        self.triangles[t + 1] = I::from_usize(i1);
+        self.triangles[t + 2] = I::from_usize(i2);
+
+        self.link(t, a);
+        self.link(t + 1, b);
+        self.link(t + 2, c);
+
+        self.triangles_len += 3;
+
+        t
+    }
+
+    // Link two halfedges
+    fn link(&mut self, a: usize, b: I) {
+        self.halfedges[a] = b;
+        if b != I::NONE {
+            self.halfedges[b.to_usize()] = I::from_usize(a);
+        }
+    }
+
+    // Recursively legalize triangles to maintain the Delaunay property
+    fn legalize(&mut self, coords: &[f64], a: usize) -> usize {
+        let mut edge_stack = [I::from_usize(0); EDGE_STACK_SIZE];
+        let mut stack_size = 0;
+        let mut a = a;
+        let mut ar;
+
+        // Recursion eliminated with a fixed-size stack
+        loop {
+            let b = self.halfedges[a];
+
+            // If the pair of triangles doesn't satisfy the Delaunay condition,
+            // flip them, then do the same check/flip recursively for the new pair
+            let a0 = a - a % 3;
+            ar = a0 + (a + 2) % 3;
+
+            if b == I::NONE {
+                // Convex hull edge
+                if stack_size == 0 {
+                    break;
+                }
+                stack_size -= 1;
+                a = edge_stack[stack_size].to_usize();
+                continue;
+            }
+
+            let b = b.to_usize();
+            let b0 = b - b % 3;
+            let al = a0 + (a + 1) % 3;
+            let bl = b0 + (b + 2) % 3;
+
+            let p0 = self.triangles[ar].to_usize();
+            let pr = self.triangles[a].to_usize();
+            let pl = self.triangles[al].to_usize();
+            let p1 = self.triangles[bl].to_usize();
+
+            // Check if the Delaunay condition is violated
+            let illegal = in_circle(
+                (coords[2 * p0], coords[2 * p0 + 1]),
+                (coords[2 * pr], coords[2 * pr + 1]),
+                (coords[2 * pl], coords[2 * pl + 1]),
+                (coords[2 * p1], coords[2 * p1 + 1]),
+            );
+
+            if illegal {
+                // Flip the edge
+                self.triangles[a] = I::from_usize(p1);
+                self.triangles[b] = I::from_usize(p0);
+
+                let hbl = self.halfedges[bl];
+
+                // Edge swapped on the other side of the hull (rare)
+                // Fix the halfedge reference
+                if hbl == I::NONE {
+                    let mut e = self.hull_start;
+                    loop {
+                        if self.hull_tri[e].to_usize() == bl {
+                            self.hull_tri[e] = I::from_usize(a);
+                            break;
+                        }
+                        e = self.hull_prev[e].to_usize();
+                        if e == self.hull_start {
+                            break;
+                        }
+                    }
+                }
+
+                self.link(a, hbl);
+                self.link(b, self.halfedges[ar]);
+                self.link(ar, I::from_usize(bl));
+
+                let br = b0 + (b + 1) % 3;
+
+                if stack_size < EDGE_STACK_SIZE {
+                    edge_stack[stack_size] = I::from_usize(br);
+                    stack_size += 1;
+                }
+            } else {
+                if stack_size == 0 {
+                    break;
+                }
+                stack_size -= 1;
+                a = edge_stack[stack_size].to_usize();
+            }
+        }
+
+        ar
+    }
+}
+
+cfg_if! {
+    if #[cfg(feature = "rayon")] {
+        use rayon::prelude::*;
+
+        // Comparison sort of point ids by increasing distance, then by id
+        fn comparison_sort<I: Index>(ids: &mut [I], dists: &[f64]) {
+            ids.par_sort_unstable_by(|&a, &b| {
+                dists[a.to_usize()]
+                    .total_cmp(&dists[b.to_usize()])
+                    .then(a.cmp(&b))
+            });
+        }
+    } else {
+        // Comparison sort of point ids by increasing distance, then by id
+        fn comparison_sort<I: Index>(ids: &mut [I], dists: &[f64]) {
+            ids.sort_unstable_by(|&a, &b| {
+                dists[a.to_usize()]
+                    .total_cmp(&dists[b.to_usize()])
+                    .then(a.cmp(&b))
+            });
+        }
+    }
+}
+
+/// Sort point ids by increasing distance with the chosen algorithm
+fn sort_by_distance<I: Index>(ids: &mut [I], dists: &mut [f64], sort: Sort) {
+    match sort {
+        Sort::Radix => radix_sort(ids, dists),
+        Sort::Comparison => comparison_sort(ids, dists),
+        Sort::Quicksort => {
+            if !ids.is_empty() {
+                quicksort(ids, dists, 0, ids.len() - 1)
+            }
+        }
+    }
+}
+
+/// LSD radix sort of point ids by distance
+///
+/// Sorts on the bits of the distances, mapped so that they compare as
+/// unsigned integers in the order of `f64::total_cmp`, 11 bits per pass.
+/// Passes where every key has the same digit are skipped, which saves most
+/// of the high bits for points of similar magnitude. The sort is stable, so
+/// ids sorted in increasing order are ordered by distance, then by id.
+fn radix_sort<I: Index>(ids: &mut [I], dists: &[f64]) {
+    const BITS: usize = 11;
+    const MASK: u64 = (1 << BITS) - 1;
+
+    let n = ids.len();
+    let key = |d: f64| {
+        let bits = d.to_bits();
+        if bits >> 63 == 1 {
+            !bits
+        } else {
+            bits | 1 << 63
+        }
+    };
+
+    let mut keys: Vec<u64> = ids.iter().map(|&i| key(dists[i.to_usize()])).collect();
+    let mut order = ids.to_vec();
+    let mut sorted_keys = vec![0; n];
+    let mut sorted_ids = vec![I::from_usize(0); n];
+    let mut counts = vec![0; 1 << BITS];
+
+    for shift in (0..64).step_by(BITS) {
+        counts.fill(0);
+        for &k in &keys {
+            counts[((k >> shift) & MASK) as usize] += 1;
+        }
+        if counts.contains(&n) {
+            continue;
+        }
+
+        // Turn the counts into the start of each digit in the output
+        let mut start = 0;
+        for count in counts.iter_mut() {
+            let c = *count;
+            *count = start;
+            start += c;
+        }
+
+        for (&k, &id) in keys.iter().zip(&order) {
+            let slot = &mut counts[((k >> shift) & MASK) as usize];
+            sorted_keys[*slot] = k;
+            sorted_ids[*slot] = id;
+            *slot += 1;
+        }
+        core::mem::swap(&mut keys, &mut sorted_keys);
+        core::mem::swap(&mut order, &mut sorted_ids);
+    }
+
+    ids.copy_from_slice(&order);
+}
+
+/// QuickSort implementation for sorting points by distance
+///
+/// This sorts the ids array based on values in the dists array.
+/// Uses insertion sort for small arrays to improve performance.
+fn quicksort<I: Index>(ids: &mut [I], dists: &mut [f64], left: usize, right: usize) {
+    if right <= left {
+        return;
+    }
+
+    if right - left <= 20 {
+        // Insertion sort for small arrays
+        for i in left + 1..=right {
+            let temp = ids[i];
+            let temp_dist = dists[temp.to_usize()];
+            let mut j = i;
+            while j > left && dists[ids[j - 1].to_usize()] > temp_dist {
+                ids[j] = ids[j - 1];
+                j -= 1;
+            }
+            ids[j] = temp;
+        }
+    } else {
+        // QuickSort for larger arrays
+        let median = (left + right) >> 1;
+        let mut i = left + 1;
+        let mut j = right;
+
+        ids.swap(median, i);
+
+        if dists[ids[left].to_usize()] > dists[ids[right].to_usize()] {
+            ids.swap(left, right);
+        }
+        if dists[ids[i].to_usize()] > dists[ids[right].to_usize()] {
+            ids.swap(i, right);
+        }
+        if dists[ids[left].to_usize()] > dists[ids[i].to_usize()] {
+            ids.swap(left, i);
+        }
+
+        let temp = ids[i];
+        let temp_dist = dists[temp.to_usize()];
+
+        let mut running = true;
+        while running {
+            loop {
+                i += 1;
+                if i >= right || dists[ids[i].to_usize()] >= temp_dist {
+                    break;
+                }
+            }
+            loop {
+                j -= 1;
+                if j <= left || dists[ids[j].to_usize()] <= temp_dist {
+                    break;
+                }
+            }
+            if j < i {
+                running = false;
+            } else {
+                ids.swap(i, j);
+            }
+        }
+
+        ids[left + 1] = ids[j];
+        ids[j] = temp;
+
+        if right - i + 1 >= j - left {
+            quicksort(ids, dists, i, right);
+            quicksort(ids, dists, left, j.saturating_sub(1));
+        } else {
+            quicksort(ids, dists, left, j.saturating_sub(1));
+            quicksort(ids, dists, i, right);
+        }
+    }
+}
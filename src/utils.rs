@@ -3,14 +3,14 @@
 //! This module provides utility functions for WebAssembly support,
 //! specifically for error handling and debugging.
 //!
-//! When compiling to WebAssembly, it leverages the console_error_panic_hook
-//! crate to provide better error messages in the browser console.
-//! For non-WASM targets, it provides no-op implementations.
+//! When compiling to WebAssembly with the `wasm` feature, it leverages the
+//! console_error_panic_hook crate to provide better error messages in the
+//! browser console. Otherwise, it provides no-op implementations.
 
 use cfg_if::cfg_if;
 
 cfg_if! {
-    if #[cfg(target_arch = "wasm32")] {
+    if #[cfg(all(feature = "wasm", target_arch = "wasm32"))] {
         extern crate console_error_panic_hook;
 
         /// Sets up the panic hook in WASM environment
@@ -0,0 +1,284 @@
+//! Triangulation invariant checks
+//!
+//! [`Delaunator::validate`] inspects the output arrays of a triangulation and
+//! reports every way in which they fail to describe a valid Delaunay
+//! triangulation. It is meant for debugging, since it runs in linear time
+//! but with exact predicates, and works on arrays that were modified or
+//! deserialized after triangulating, so corrupted indices are reported
+//! instead of causing panics.
+
+use alloc::{collections::BTreeMap, vec, vec::Vec};
+use core::fmt;
+
+use robust::{incircle, orient2d, Coord};
+
+use crate::power::power;
+use crate::{next_halfedge, prev_halfedge, Delaunator};
+
+/// A broken invariant of a triangulation
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Violation {
+    /// `triangles` and `halfedges` differ in length, or their length is not
+    /// a multiple of 3
+    Length { triangles: usize, halfedges: usize },
+    /// Halfedge `edge` starts at a point that does not exist
+    PointOutOfRange { edge: usize, point: u32 },
+    /// The opposite of halfedge `edge` is neither -1 nor a halfedge
+    TwinOutOfRange { edge: usize, twin: i32 },
+    /// The opposite of halfedge `edge` does not point back to it
+    AsymmetricTwin { edge: usize, twin: usize },
+    /// Halfedge `edge` and its opposite do not join the same two points in
+    /// reverse directions
+    MismatchedTwin { edge: usize, twin: usize },
+    /// Triangle `triangle` is degenerate or winds against the others
+    Orientation { triangle: usize },
+    /// The point opposite to interior halfedge `edge` lies strictly inside
+    /// the circumcircle of the triangle of `edge`, or for a weighted
+    /// triangulation, violates the power test
+    NotDelaunay { edge: usize },
+    /// `hull` contains a point that does not exist
+    HullPointOutOfRange { index: usize, point: u32 },
+    /// Point `hull[index]` appears more than once in `hull`
+    HullPointRepeated { index: usize },
+    /// The hull edge from `hull[index]` to the next hull point is not a
+    /// halfedge without opposite
+    HullEdgeNotBoundary { index: usize },
+    /// Halfedge `edge` has no opposite but is not a hull edge
+    BoundaryEdgeNotOnHull { edge: usize },
+}
+
+/// Every violation found by [`Delaunator::validate`]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ValidationReport {
+    pub violations: Vec<Violation>,
+}
+
+impl Violation {
+    /// Whether the violation breaks the connectivity of the triangulation,
+    /// rather than only its geometry
+    pub fn is_structural(&self) -> bool {
+        !matches!(
+            self,
+            Violation::Orientation { .. } | Violation::NotDelaunay { .. }
+        )
+    }
+}
+
+impl ValidationReport {
+    pub fn is_valid(&self) -> bool {
+        self.violations.is_empty()
+    }
+
+    /// Whether every halfedge, opposite and hull index is consistent,
+    /// ignoring orientation and the empty circle property
+    pub fn is_structurally_valid(&self) -> bool {
+        self.violations.iter().all(|v| !v.is_structural())
+    }
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            Violation::Length {
+                triangles,
+                halfedges,
+            } => write!(
+                f,
+                "triangles has length {triangles} and halfedges has length {halfedges}"
+            ),
+            Violation::PointOutOfRange { edge, point } => {
+                write!(f, "halfedge {edge} starts at missing point {point}")
+            }
+            Violation::TwinOutOfRange { edge, twin } => {
+                write!(f, "halfedge {edge} has missing opposite {twin}")
+            }
+            Violation::AsymmetricTwin { edge, twin } => {
+                write!(
+                    f,
+                    "halfedge {edge} has opposite {twin} which does not point back"
+                )
+            }
+            Violation::MismatchedTwin { edge, twin } => write!(
+                f,
+                "halfedge {edge} and its opposite {twin} do not join the same points"
+            ),
+            Violation::Orientation { triangle } => {
+                write!(f, "triangle {triangle} is degenerate or inverted")
+            }
+            Violation::NotDelaunay { edge } => {
+                write!(f, "interior halfedge {edge} is not locally Delaunay")
+            }
+            Violation::HullPointOutOfRange { index, point } => {
+                write!(f, "hull[{index}] is missing point {point}")
+            }
+            Violation::HullPointRepeated { index } => {
+                write!(f, "hull[{index}] appears earlier in the hull")
+            }
+            Violation::HullEdgeNotBoundary { index } => {
+                write!(f, "hull edge from hull[{index}] is not a boundary halfedge")
+            }
+            Violation::BoundaryEdgeNotOnHull { edge } => {
+                write!(f, "boundary halfedge {edge} is not a hull edge")
+            }
+        }
+    }
+}
+
+impl fmt::Display for ValidationReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.violations.is_empty() {
+            return write!(f, "valid triangulation");
+        }
+        let n = self.violations.len();
+        write!(f, "{n} violation{}", if n == 1 { "" } else { "s" })?;
+        for v in &self.violations {
+            write!(f, "\n  {v}")?;
+        }
+        Ok(())
+    }
+}
+
+impl Delaunator {
+    /// Checks the invariants of the triangulation
+    ///
+    /// Verifies that the arrays are consistent in length and indices, that
+    /// halfedges and their opposites pair up symmetrically, that every
+    /// triangle winds the same way, that every interior edge satisfies the
+    /// empty circle property (the power test for a weighted triangulation),
+    /// and that `hull` is a closed loop made of exactly the halfedges
+    /// without opposite. Orientation and circle tests use exact predicates,
+    /// so cocircular points are accepted in either configuration.
+    pub fn validate(&self) -> ValidationReport {
+        let mut violations = Vec::new();
+        let n = self.coords.len() / 2;
+
+        if self.triangles.len() != self.halfedges.len() || !self.triangles.len().is_multiple_of(3) {
+            violations.push(Violation::Length {
+                triangles: self.triangles.len(),
+                halfedges: self.halfedges.len(),
+            });
+            return ValidationReport { violations };
+        }
+
+        // Structure: indices, then symmetric and matching opposites
+        let mut intact = vec![true; self.triangles.len() / 3];
+        for (e, &point) in self.triangles.iter().enumerate() {
+            if point as usize >= n {
+                violations.push(Violation::PointOutOfRange { edge: e, point });
+                intact[e / 3] = false;
+            }
+        }
+        for (e, &twin) in self.halfedges.iter().enumerate() {
+            if twin < -1 || twin >= 0 && twin as usize >= self.halfedges.len() {
+                violations.push(Violation::TwinOutOfRange { edge: e, twin });
+                intact[e / 3] = false;
+            }
+        }
+        for (e, &twin) in self.halfedges.iter().enumerate() {
+            if !intact[e / 3] || twin == -1 {
+                continue;
+            }
+
+            let twin = twin as usize;
+            if self.halfedges[twin] != e as i32 {
+                violations.push(Violation::AsymmetricTwin { edge: e, twin });
+            } else if e < twin && intact[twin / 3] {
+                let start = self.triangles[e];
+                let end = self.triangles[next_halfedge(e)];
+                if self.triangles[twin] != end || self.triangles[next_halfedge(twin)] != start {
+                    violations.push(Violation::MismatchedTwin { edge: e, twin });
+                }
+            }
+        }
+
+        // Geometry: orientation, then the empty circle property
+        for (t, _) in intact.iter().enumerate().filter(|(_, &ok)| ok) {
+            let [a, b, c] = [0, 1, 2].map(|k| self.point(self.triangles[3 * t + k]));
+            if orient2d(a, b, c) >= 0.0 {
+                violations.push(Violation::Orientation { triangle: t });
+            }
+        }
+        for (e, &twin) in self.halfedges.iter().enumerate() {
+            if twin < 0 || twin as usize <= e || !intact[e / 3] {
+                continue;
+            }
+            let twin = twin as usize;
+            if !intact[twin / 3] || self.halfedges[twin] != e as i32 {
+                continue;
+            }
+
+            let [a, b, c] = [e, next_halfedge(e), prev_halfedge(e)].map(|k| self.triangles[k]);
+            let p = self.triangles[prev_halfedge(twin)];
+            if !self.is_locally_delaunay(a, b, c, p) {
+                violations.push(Violation::NotDelaunay { edge: e });
+            }
+        }
+
+        // Hull: distinct points, joined by exactly the boundary halfedges
+        // when there are triangles
+        let mut seen = BTreeMap::new();
+        for (index, &point) in self.hull.iter().enumerate() {
+            if point as usize >= n {
+                violations.push(Violation::HullPointOutOfRange { index, point });
+            } else if seen.insert(point, index).is_some() {
+                violations.push(Violation::HullPointRepeated { index });
+            }
+        }
+
+        if !self.triangles.is_empty() {
+            let mut boundary: BTreeMap<u32, usize> = BTreeMap::new();
+            for (e, &twin) in self.halfedges.iter().enumerate() {
+                if twin == -1 && intact[e / 3] {
+                    if let Some(other) = boundary.insert(self.triangles[e], e) {
+                        // Two boundary edges leaving the same point cannot
+                        // both be followed by the hull
+                        violations.push(Violation::BoundaryEdgeNotOnHull { edge: other });
+                    }
+                }
+            }
+
+            for (index, &point) in self.hull.iter().enumerate() {
+                let next = self.hull[(index + 1) % self.hull.len()];
+                match boundary.get(&point) {
+                    Some(&e) if self.triangles[next_halfedge(e)] == next => {
+                        boundary.remove(&point);
+                    }
+                    _ => violations.push(Violation::HullEdgeNotBoundary { index }),
+                }
+            }
+
+            let mut remaining: Vec<usize> = boundary.into_values().collect();
+            remaining.sort_unstable();
+            violations.extend(
+                remaining
+                    .into_iter()
+                    .map(|edge| Violation::BoundaryEdgeNotOnHull { edge }),
+            );
+        }
+
+        ValidationReport { violations }
+    }
+
+    fn point(&self, i: u32) -> Coord<f64> {
+        Coord {
+            x: self.coords[2 * i as usize],
+            y: self.coords[2 * i as usize + 1],
+        }
+    }
+
+    // Whether point p, across the edge from a to b, stays out of the circle
+    // through a, b and c, which wind clockwise with the y axis pointing up
+    fn is_locally_delaunay(&self, a: u32, b: u32, c: u32, p: u32) -> bool {
+        if self.weights.is_empty() {
+            return incircle(self.point(a), self.point(b), self.point(c), self.point(p)) >= 0.0;
+        }
+
+        // Exact power test, the same as the one the triangulation uses
+        let [pa, pb, pc, pp] = [a, b, c, p].map(|i| {
+            let q = self.point(i);
+            (q.x, q.y)
+        });
+        let w = [a, b, c, p].map(|i| self.weight(i as usize));
+        power(pa, pb, pc, pp, w) >= 0.0
+    }
+}
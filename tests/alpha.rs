@@ -0,0 +1,268 @@
+//! Alpha shapes and concave hulls checked against the points they enclose
+
+mod common;
+
+use delaunator_rs::Delaunator;
+use proptest::prelude::*;
+use robust::{orient2d, Coord};
+
+fn random() -> impl Strategy<Value = Vec<f64>> {
+    prop::collection::vec((-100.0..100.0, -100.0..100.0), 3..80)
+        .prop_map(|points| points.into_iter().flat_map(|(x, y)| [x, y]).collect())
+}
+
+// Unit lattice over [0, 9] x [0, 9] without the points for which `removed`
+// holds
+fn lattice(removed: impl Fn(i32, i32) -> bool) -> Vec<f64> {
+    (0..10)
+        .flat_map(|x| (0..10).map(move |y| (x, y)))
+        .filter(|&(x, y)| !removed(x, y))
+        .flat_map(|(x, y)| [x as f64, y as f64])
+        .collect()
+}
+
+fn coord(coords: &[f64], i: u32) -> Coord<f64> {
+    Coord {
+        x: coords[2 * i as usize],
+        y: coords[2 * i as usize + 1],
+    }
+}
+
+// Shoelace area of a ring, positive when it winds counterclockwise
+fn ring_area(coords: &[f64], ring: &[u32]) -> f64 {
+    let first = coord(coords, ring[0]);
+    ring.windows(2)
+        .map(|e| orient2d(first, coord(coords, e[0]), coord(coords, e[1])))
+        .sum::<f64>()
+        / 2.0
+}
+
+fn triangle_area(d: &Delaunator, coords: &[f64], t: usize) -> f64 {
+    let [a, b, c] = [0, 1, 2].map(|k| coord(coords, d.triangles[3 * t + k]));
+    orient2d(a, b, c).abs() / 2.0
+}
+
+// Whether p lies on the closed segment ab
+fn on_segment(a: Coord<f64>, b: Coord<f64>, p: Coord<f64>) -> bool {
+    orient2d(a, b, p) == 0.0
+        && p.x >= a.x.min(b.x)
+        && p.x <= a.x.max(b.x)
+        && p.y >= a.y.min(b.y)
+        && p.y <= a.y.max(b.y)
+}
+
+// Whether the closed segments ab and cd share a point
+fn segments_meet(a: Coord<f64>, b: Coord<f64>, c: Coord<f64>, d: Coord<f64>) -> bool {
+    let (o1, o2) = (orient2d(a, b, c), orient2d(a, b, d));
+    let (o3, o4) = (orient2d(c, d, a), orient2d(c, d, b));
+    (o1 * o2 < 0.0 && o3 * o4 < 0.0)
+        || on_segment(a, b, c)
+        || on_segment(a, b, d)
+        || on_segment(c, d, a)
+        || on_segment(c, d, b)
+}
+
+// Checks that the ring has distinct corners and that only consecutive edges
+// meet, at their shared corner
+fn check_simple(coords: &[f64], ring: &[u32]) {
+    let n = ring.len();
+    assert!(n >= 3, "{ring:?}");
+    let mut corners = ring.to_vec();
+    corners.sort_unstable();
+    corners.dedup();
+    assert_eq!(corners.len(), n, "repeated corner in {ring:?}");
+
+    let edge = |k: usize| (coord(coords, ring[k]), coord(coords, ring[(k + 1) % n]));
+    for i in 0..n {
+        for j in i + 2..n {
+            if i == 0 && j == n - 1 {
+                continue;
+            }
+            let ((a, b), (c, d)) = (edge(i), edge(j));
+            assert!(!segments_meet(a, b, c, d), "edges {i} and {j} cross");
+        }
+    }
+}
+
+// Whether p lies inside the ring or on its boundary, by winding number
+fn encloses(coords: &[f64], ring: &[u32], p: Coord<f64>) -> bool {
+    let n = ring.len();
+    let mut winding = 0;
+    for k in 0..n {
+        let (a, b) = (coord(coords, ring[k]), coord(coords, ring[(k + 1) % n]));
+        if on_segment(a, b, p) {
+            return true;
+        }
+        if a.y <= p.y {
+            if b.y > p.y && orient2d(a, b, p) > 0.0 {
+                winding += 1;
+            }
+        } else if b.y <= p.y && orient2d(a, b, p) < 0.0 {
+            winding -= 1;
+        }
+    }
+    winding != 0
+}
+
+// Checks that the concave hull is a simple ring winding like the hull and
+// enclosing every point
+fn check_concave_hull(d: &Delaunator, coords: &[f64], ring: &[u32]) {
+    check_simple(coords, ring);
+    assert!(ring_area(coords, ring) * ring_area(coords, &d.hull) > 0.0);
+    for i in 0..coords.len() as u32 / 2 {
+        assert!(encloses(coords, ring, coord(coords, i)), "point {i}");
+    }
+}
+
+// Same cycle up to the starting point
+fn assert_same_cycle(actual: &[u32], expected: &[u32]) {
+    assert_eq!(actual.len(), expected.len());
+    let start = actual.iter().position(|&i| i == expected[0]).unwrap();
+    let rotated: Vec<u32> = actual[start..]
+        .iter()
+        .chain(&actual[..start])
+        .copied()
+        .collect();
+    assert_eq!(rotated, expected);
+}
+
+// Lengths of the ring edges
+fn edge_lengths(coords: &[f64], ring: &[u32]) -> Vec<f64> {
+    (0..ring.len())
+        .map(|k| {
+            let (a, b) = (
+                coord(coords, ring[k]),
+                coord(coords, ring[(k + 1) % ring.len()]),
+            );
+            (b.x - a.x).hypot(b.y - a.y)
+        })
+        .collect()
+}
+
+proptest! {
+    // The kept triangles cover the outer rings minus the holes
+    #[test]
+    fn alpha_shape_of_random_points(coords in random(), alpha in 1.0..200.0) {
+        let d = common::triangulate(&coords);
+        let shape = d.alpha_shape(alpha);
+
+        let mut kept = vec![false; d.triangles.len() / 3];
+        for &t in &shape.triangles {
+            kept[t] = true;
+        }
+        for (t, &kept) in kept.iter().enumerate() {
+            let [a, b, c] = [0, 1, 2].map(|k| coord(&coords, d.triangles[3 * t + k]));
+            let (ax, ay) = (b.x - a.x, b.y - a.y);
+            let (bx, by) = (c.x - a.x, c.y - a.y);
+            let (cx, cy) = (c.x - b.x, c.y - b.y);
+            let r = (ax.hypot(ay) * bx.hypot(by) * cx.hypot(cy))
+                / (2.0 * orient2d(a, b, c).abs());
+            if (r - alpha).abs() > 1e-9 * alpha {
+                prop_assert_eq!(kept, r <= alpha);
+            }
+        }
+
+        let covered: f64 = shape.triangles.iter().map(|&t| triangle_area(&d, &coords, t)).sum();
+        let outer: f64 = shape.outer.iter().map(|r| ring_area(&coords, r)).sum();
+        let holes: f64 = shape.holes.iter().map(|r| ring_area(&coords, r)).sum();
+        prop_assert!(outer <= 0.0 && holes >= 0.0);
+        prop_assert!((covered + outer + holes).abs() <= 1e-9 * 4e4);
+    }
+
+    #[test]
+    fn concave_hull_of_random_points(
+        coords in random(),
+        max_edge in prop_oneof![Just(0.0), 0.0..300.0, Just(f64::INFINITY)],
+    ) {
+        let d = common::triangulate(&coords);
+        prop_assume!(!d.triangles.is_empty());
+        let ring = d.concave_hull(max_edge);
+        check_concave_hull(&d, &coords, &ring);
+        if max_edge == f64::INFINITY {
+            assert_same_cycle(&ring, &d.hull);
+        }
+    }
+}
+
+// The triangles spanning the hole have a circumradius of at least 2.5, and
+// the lattice ones of sqrt(2) / 2
+#[test]
+fn alpha_shape_of_lattice_with_hole() {
+    let hole = |x: i32, y: i32| (3..=6).contains(&x) && (3..=6).contains(&y);
+    let coords = lattice(hole);
+    let d = common::triangulate(&coords);
+
+    let shape = d.alpha_shape(1.0);
+    assert_eq!(shape.outer.len(), 1);
+    assert_eq!(shape.holes.len(), 1);
+    assert_eq!(ring_area(&coords, &shape.outer[0]), -81.0);
+    check_simple(&coords, &shape.holes[0]);
+    for &i in &shape.holes[0] {
+        let p = coord(&coords, i);
+        assert_eq!((p.x - 4.5).abs().max((p.y - 4.5).abs()), 2.5, "{p:?}");
+    }
+    let covered: f64 = shape
+        .triangles
+        .iter()
+        .map(|&t| triangle_area(&d, &coords, t))
+        .sum();
+    assert_eq!(covered, 81.0 - ring_area(&coords, &shape.holes[0]));
+
+    // Wide enough for every triangle, or too narrow for any
+    let shape = d.alpha_shape(1e9);
+    assert_eq!(shape.triangles.len(), d.triangles.len() / 3);
+    assert_eq!(shape.outer.len(), 1);
+    assert_same_cycle(&shape.outer[0], &d.hull);
+    assert!(shape.holes.is_empty());
+    assert!(d.alpha_shape(0.5).triangles.is_empty());
+}
+
+// A notch cut into the top of the lattice, which the convex hull spans with
+// an edge of length 5. The concave hull follows the notch except for its two
+// bottom corners, cut by lattice diagonals.
+#[test]
+fn concave_hull_of_notched_lattice() {
+    let notch = |x: i32, y: i32| (3..=6).contains(&x) && y >= 4;
+    let coords = lattice(notch);
+    let d = common::triangulate(&coords);
+
+    let ring = d.concave_hull(1.5);
+    check_concave_hull(&d, &coords, &ring);
+    assert!(edge_lengths(&coords, &ring).iter().all(|&l| l <= 1.5));
+    assert_eq!(ring_area(&coords, &ring), -(81.0 - 6.0 * 5.0 + 2.0 * 0.5));
+
+    let ring = d.concave_hull(f64::INFINITY);
+    assert_same_cycle(&ring, &d.hull);
+    assert_eq!(ring_area(&coords, &ring), -81.0);
+}
+
+#[test]
+fn parameters_out_of_range() {
+    let coords = lattice(|x, y| (3..=6).contains(&x) && y >= 4);
+    let d = common::triangulate(&coords);
+
+    for alpha in [0.0, -1.0, -1e9, f64::NAN] {
+        assert_eq!(d.alpha_shape(alpha), Default::default(), "{alpha}");
+    }
+
+    let eroded = d.concave_hull(0.0);
+    check_concave_hull(&d, &coords, &eroded);
+    for max_edge in [-1.0, -1e9, f64::NAN] {
+        assert_eq!(d.concave_hull(max_edge), eroded, "{max_edge}");
+    }
+}
+
+// Far from the origin, an absolute shoelace sum took holes for outer rings
+#[test]
+fn large_offset() {
+    let hole = |x: i32, y: i32| (3..=6).contains(&x) && (3..=6).contains(&y);
+    let coords: Vec<f64> = lattice(hole)
+        .chunks_exact(2)
+        .flat_map(|p| [p[0] + 1e9, p[1] - 3e9])
+        .collect();
+    let d = common::triangulate(&coords);
+
+    let shape = d.alpha_shape(1.0);
+    assert_eq!(shape.outer.len(), 1);
+    assert_eq!(shape.holes.len(), 1);
+}
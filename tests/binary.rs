@@ -0,0 +1,136 @@
+//! Binary encoding round trips, corrupted input and borrowed views
+#![cfg(feature = "std")]
+
+mod common;
+
+use delaunator_rs::binary::{FormatError, View};
+use delaunator_rs::{Delaunator, Error};
+
+// Binary encoding of a triangulation
+fn bytes_of(d: &Delaunator) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    d.write_to(&mut bytes).unwrap();
+    bytes
+}
+
+// Copy of `bytes` starting 8-byte aligned
+fn aligned(bytes: &[u8]) -> Vec<u64> {
+    let mut words = vec![0u64; bytes.len().div_ceil(8)];
+    bytemuck::cast_slice_mut::<u64, u8>(&mut words)[..bytes.len()].copy_from_slice(bytes);
+    words
+}
+
+#[test]
+fn binary_round_trip() {
+    let coords = vec![0.0, 0.0, 1.0, 0.0, 1.0, 1.0, 0.0, 1.0, 0.5, 0.5];
+    let weights = vec![0.0, 0.0, 0.0, 0.0, -1.0];
+    let elevations = [1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0, 10.0];
+
+    for (d, weights) in [
+        (common::triangulate(&coords), vec![]),
+        (
+            Delaunator::with_weights(coords.clone(), weights.clone()).unwrap(),
+            weights,
+        ),
+    ] {
+        let mut bytes = Vec::new();
+        d.write_with_attributes(&mut bytes, &elevations).unwrap();
+
+        let (read, attributes) = Delaunator::read_with_attributes(&bytes[..]).unwrap();
+        assert_eq!(read.coords(), d.coords());
+        assert_eq!(read.triangles, d.triangles);
+        assert_eq!(read.halfedges, d.halfedges);
+        assert_eq!(read.hull, d.hull);
+        assert_eq!(read.hidden, d.hidden);
+        assert_eq!(attributes, elevations);
+
+        // Borrowed in place, the same arrays
+        let words = aligned(&bytes);
+        let view = View::new(&bytemuck::cast_slice(&words)[..bytes.len()]).unwrap();
+        assert_eq!(view.coords(), d.coords());
+        assert_eq!(view.weights(), weights);
+        assert_eq!(view.triangles(), &d.triangles[..]);
+        assert_eq!(view.halfedges(), &d.halfedges[..]);
+        assert_eq!(view.hull(), &d.hull[..]);
+        assert_eq!(view.hidden(), &d.hidden[..]);
+        assert_eq!(view.attribute_count(), 2);
+        assert_eq!(view.attributes(), elevations);
+        assert_eq!(view.to_delaunator().unwrap().triangles, d.triangles);
+    }
+
+    let mut bytes = Vec::new();
+    let error = common::triangulate(&coords)
+        .write_with_attributes(&mut bytes, &[1.0, 2.0, 3.0])
+        .unwrap_err();
+    assert_eq!(error.kind(), std::io::ErrorKind::InvalidInput);
+}
+
+#[test]
+fn binary_corruption() {
+    let coords = vec![0.0, 0.0, 1.0, 0.0, 1.0, 1.0, 0.0, 1.0, 0.4, 0.6];
+    let d = common::triangulate(&coords);
+    let bytes = bytes_of(&d);
+    let format_error = |bytes: &[u8]| {
+        let error = Delaunator::read_from(bytes).unwrap_err();
+        assert_eq!(error.kind(), std::io::ErrorKind::InvalidData);
+        let words = aligned(bytes);
+        let view = View::new(&bytemuck::cast_slice(&words)[..bytes.len()]);
+        (
+            *error
+                .into_inner()
+                .unwrap()
+                .downcast::<FormatError>()
+                .unwrap(),
+            view.err(),
+        )
+    };
+
+    let mut corrupted = bytes.clone();
+    corrupted[60] ^= 1;
+    assert_eq!(
+        format_error(&corrupted),
+        (FormatError::Checksum, Some(FormatError::Checksum))
+    );
+    let mut corrupted = bytes.clone();
+    corrupted[0] = b'X';
+    assert_eq!(
+        format_error(&corrupted),
+        (
+            FormatError::NotTriangulation,
+            Some(FormatError::NotTriangulation)
+        )
+    );
+    let mut corrupted = bytes.clone();
+    corrupted[4] = 2;
+    assert_eq!(
+        format_error(&corrupted),
+        (
+            FormatError::UnsupportedVersion(2),
+            Some(FormatError::UnsupportedVersion(2))
+        )
+    );
+
+    // Checksum intact, but a flipped triangle
+    let mut flipped = d;
+    flipped.triangles.swap(0, 1);
+    assert_eq!(
+        format_error(&bytes_of(&flipped)),
+        (
+            FormatError::Triangulation(Error::InvalidTriangulation),
+            None
+        )
+    );
+
+    // Truncated data, and an absurd count that must not be allocated
+    let error = Delaunator::read_from(&bytes[..bytes.len() - 1]).unwrap_err();
+    assert_eq!(error.kind(), std::io::ErrorKind::UnexpectedEof);
+    let mut huge = bytes.clone();
+    huge[16..24].copy_from_slice(&(1u64 << 40).to_le_bytes());
+    assert!(Delaunator::read_from(&huge[..]).is_err());
+    assert_eq!(View::new(&bytes[..40]).unwrap_err(), FormatError::Length);
+
+    // One byte off from an aligned start
+    let words = aligned(&[&[0], &bytes[..]].concat());
+    let shifted = &bytemuck::cast_slice::<u64, u8>(&words)[1..bytes.len() + 1];
+    assert_eq!(View::new(shifted).unwrap_err(), FormatError::Misaligned);
+}
@@ -0,0 +1,259 @@
+//! Rotating calipers checked against brute force over every point
+
+use delaunator_rs::calipers::{
+    diameter, min_area_rect, min_enclosing_circle, min_perimeter_rect, width, OrientedRect,
+};
+use delaunator_rs::hull::convex_hull;
+use proptest::prelude::*;
+
+fn flatten(points: Vec<(f64, f64)>) -> Vec<f64> {
+    points.into_iter().flat_map(|(x, y)| [x, y]).collect()
+}
+
+fn random() -> impl Strategy<Value = Vec<f64>> {
+    prop::collection::vec((-100.0..100.0, -100.0..100.0), 1..12).prop_map(flatten)
+}
+
+// Integer steps along an integer direction, so exactly collinear
+fn collinear() -> impl Strategy<Value = Vec<f64>> {
+    (
+        (-10..10i32, -10..10i32).prop_filter("nonzero direction", |&d| d != (0, 0)),
+        prop::collection::vec(-10..10i32, 1..12),
+    )
+        .prop_map(|((dx, dy), steps)| {
+            steps
+                .into_iter()
+                .flat_map(|t| [(5 + t * dx) as f64, (-2 + t * dy) as f64])
+                .collect()
+        })
+}
+
+// Few distinct points, each repeated
+fn duplicates() -> impl Strategy<Value = Vec<f64>> {
+    prop::collection::vec((-100.0..100.0, -100.0..100.0), 1..5).prop_flat_map(|points| {
+        prop::collection::vec(prop::sample::select(points), 2..20).prop_map(flatten)
+    })
+}
+
+fn points(coords: &[f64]) -> Vec<(f64, f64)> {
+    coords.chunks_exact(2).map(|p| (p[0], p[1])).collect()
+}
+
+fn distance(a: (f64, f64), b: (f64, f64)) -> f64 {
+    (b.0 - a.0).hypot(b.1 - a.1)
+}
+
+// Extents of the points along the direction from a to b and across it
+fn extents(points: &[(f64, f64)], a: (f64, f64), b: (f64, f64)) -> (f64, f64) {
+    let len = distance(a, b);
+    let (ux, uy) = ((b.0 - a.0) / len, (b.1 - a.1) / len);
+    let spread = |project: &dyn Fn((f64, f64)) -> f64| {
+        let values = points.iter().map(|&p| project(p));
+        let lo = values.clone().fold(f64::INFINITY, f64::min);
+        let hi = values.fold(f64::NEG_INFINITY, f64::max);
+        hi - lo
+    };
+    (
+        spread(&|p| p.0 * ux + p.1 * uy),
+        spread(&|p| p.1 * ux - p.0 * uy),
+    )
+}
+
+// Extents along the direction of every pair of points, which include the
+// edges of the hull, one of which a minimal rectangle has a side along
+fn pair_extents(points: &[(f64, f64)]) -> Vec<(f64, f64)> {
+    let mut all = Vec::new();
+    for (i, &a) in points.iter().enumerate() {
+        for &b in &points[i + 1..] {
+            if a != b {
+                all.push(extents(points, a, b));
+            }
+        }
+    }
+    all
+}
+
+fn rect_contains(rect: &OrientedRect, p: (f64, f64), tolerance: f64) -> bool {
+    let (cos, sin) = (rect.angle.cos(), rect.angle.sin());
+    let (dx, dy) = (p.0 - rect.center.0, p.1 - rect.center.1);
+    let along = dx * cos + dy * sin;
+    let across = dy * cos - dx * sin;
+    along.abs() <= rect.width / 2.0 + tolerance && across.abs() <= rect.height / 2.0 + tolerance
+}
+
+fn close(a: f64, b: f64) -> bool {
+    (a - b).abs() <= 1e-9 * (1.0 + a.abs().max(b.abs()))
+}
+
+// Checks every measure against brute force, with the hull in both windings
+fn check(coords: &[f64]) {
+    let points = points(coords);
+    let mut hull = convex_hull(coords);
+    let distinct = pair_extents(&points);
+
+    for _ in 0..2 {
+        // Farthest pair among all pairs of points
+        let (a, b, d) = diameter(coords, &hull).unwrap();
+        let farthest = points
+            .iter()
+            .flat_map(|&p| points.iter().map(move |&q| distance(p, q)))
+            .fold(0.0, f64::max);
+        assert!(close(d, farthest), "diameter {d} instead of {farthest}");
+        assert!(close(
+            distance(points[a as usize], points[b as usize]),
+            farthest
+        ));
+
+        // Narrowest extent across any direction
+        let w = width(coords, &hull);
+        let narrowest = distinct.iter().map(|e| e.1).fold(f64::INFINITY, f64::min);
+        if distinct.is_empty() {
+            assert_eq!(w, 0.0);
+        } else {
+            assert!(close(w, narrowest), "width {w} instead of {narrowest}");
+        }
+
+        // Smallest rectangles, which still contain every point
+        let area = min_area_rect(coords, &hull).unwrap();
+        let perimeter = min_perimeter_rect(coords, &hull).unwrap();
+        if !distinct.is_empty() {
+            let smallest = distinct
+                .iter()
+                .map(|e| e.0 * e.1)
+                .fold(f64::INFINITY, f64::min);
+            let shortest = distinct
+                .iter()
+                .map(|e| 2.0 * (e.0 + e.1))
+                .fold(f64::INFINITY, f64::min);
+            assert!(
+                close(area.area(), smallest),
+                "area {} instead of {smallest}",
+                area.area()
+            );
+            assert!(close(perimeter.perimeter(), shortest));
+        }
+        for &p in &points {
+            assert!(rect_contains(&area, p, 1e-9), "{p:?} is outside {area:?}");
+            assert!(
+                rect_contains(&perimeter, p, 1e-9),
+                "{p:?} is outside {perimeter:?}"
+            );
+        }
+
+        // Smallest circle through two or three of the points containing all
+        let circle = min_enclosing_circle(coords, &hull).unwrap();
+        for &p in &points {
+            assert!(
+                distance((circle.x, circle.y), p) <= circle.r * (1.0 + 1e-9) + 1e-9,
+                "{p:?} is outside {circle:?}"
+            );
+        }
+        assert!(close(circle.r, smallest_circle(&points)));
+
+        hull.reverse();
+    }
+}
+
+// Radius of the smallest circle containing all points, among the circles
+// through two points as a diameter or through three points
+fn smallest_circle(points: &[(f64, f64)]) -> f64 {
+    let contains_all = |x: f64, y: f64, r: f64| {
+        points
+            .iter()
+            .all(|&p| distance((x, y), p) <= r * (1.0 + 1e-9) + 1e-9)
+    };
+
+    let mut best = f64::INFINITY;
+    for (i, &a) in points.iter().enumerate() {
+        if contains_all(a.0, a.1, 0.0) {
+            best = best.min(0.0);
+        }
+        for (j, &b) in points.iter().enumerate().skip(i + 1) {
+            let (x, y) = ((a.0 + b.0) / 2.0, (a.1 + b.1) / 2.0);
+            let r = distance(a, b) / 2.0;
+            if r < best && contains_all(x, y, r) {
+                best = r;
+            }
+            for &c in &points[j + 1..] {
+                let (bx, by) = (b.0 - a.0, b.1 - a.1);
+                let (cx, cy) = (c.0 - a.0, c.1 - a.1);
+                let d = 2.0 * (bx * cy - by * cx);
+                if d == 0.0 {
+                    continue;
+                }
+                let (bl, cl) = (bx * bx + by * by, cx * cx + cy * cy);
+                let x = a.0 + (cy * bl - by * cl) / d;
+                let y = a.1 + (bx * cl - cx * bl) / d;
+                let r = distance((x, y), a);
+                if r < best && contains_all(x, y, r) {
+                    best = r;
+                }
+            }
+        }
+    }
+    best
+}
+
+proptest! {
+    #[test]
+    fn random_points(coords in random()) {
+        check(&coords);
+    }
+
+    #[test]
+    fn collinear_points(coords in collinear()) {
+        check(&coords);
+        let hull = convex_hull(&coords);
+        prop_assert_eq!(width(&coords, &hull), 0.0);
+        prop_assert_eq!(min_area_rect(&coords, &hull).unwrap().height, 0.0);
+    }
+
+    #[test]
+    fn duplicate_points(coords in duplicates()) {
+        check(&coords);
+    }
+}
+
+#[test]
+fn square() {
+    let coords = [0.0, 0.0, 2.0, 0.0, 2.0, 2.0, 0.0, 2.0, 1.0, 1.0];
+    let hull = convex_hull(&coords);
+
+    let (a, b, d) = diameter(&coords, &hull).unwrap();
+    assert!([(0, 2), (2, 0), (1, 3), (3, 1)].contains(&(a, b)));
+    assert_eq!(d, 8f64.sqrt());
+    assert_eq!(width(&coords, &hull), 2.0);
+
+    let rect = min_area_rect(&coords, &hull).unwrap();
+    assert_eq!(rect.area(), 4.0);
+    assert_eq!(rect.center, (1.0, 1.0));
+
+    let circle = min_enclosing_circle(&coords, &hull).unwrap();
+    assert_eq!((circle.x, circle.y), (1.0, 1.0));
+    assert!((circle.r - 2f64.sqrt()).abs() < 1e-15);
+}
+
+#[test]
+fn empty_hull() {
+    assert!(diameter(&[], &[]).is_none());
+    assert_eq!(width(&[], &[]), 0.0);
+    assert!(min_area_rect(&[], &[]).is_none());
+    assert!(min_perimeter_rect(&[], &[]).is_none());
+    assert!(min_enclosing_circle(&[], &[]).is_none());
+}
+
+// Far from the origin, the absolute shoelace sum of this hull rounds to
+// zero, which took it for a line
+#[test]
+fn large_offset() {
+    let (x, y) = (1e9, -3e9);
+    let coords = [x, y, x + 2.0, y, x + 2.0, y + 1.0, x, y + 1.0];
+    let hull = convex_hull(&coords);
+
+    let (_, _, d) = diameter(&coords, &hull).unwrap();
+    assert_eq!(d, 5f64.sqrt());
+    assert_eq!(width(&coords, &hull), 1.0);
+    let rect = min_area_rect(&coords, &hull).unwrap();
+    assert_eq!(rect.area(), 2.0);
+    assert_eq!(rect.center, (x + 1.0, y + 0.5));
+}
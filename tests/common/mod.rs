@@ -0,0 +1,101 @@
+//! Checks shared by the integration tests
+
+use std::collections::HashSet;
+
+use delaunator_rs::hull::convex_hull;
+use delaunator_rs::Delaunator;
+use robust::{orient2d, Coord};
+
+/// Triangulates `coords` and asserts every invariant of the result
+pub fn triangulate(coords: &[f64]) -> Delaunator {
+    let d = Delaunator::new(coords.to_vec()).expect("valid coordinates");
+    check(&d, coords);
+    d
+}
+
+/// Asserts every invariant of a triangulation of `coords`
+pub fn check(d: &Delaunator, coords: &[f64]) {
+    let report = d.validate();
+    assert!(report.is_valid(), "{report}");
+
+    let n = coords.len() / 2;
+    let point = |i: u32| (coords[2 * i as usize], coords[2 * i as usize + 1]);
+
+    // Every point is used, except duplicates of a point that is
+    let used: HashSet<u32> = d.triangles.iter().chain(&d.hull).copied().collect();
+    let positions: HashSet<(u64, u64)> = used.iter().map(|&i| key(point(i))).collect();
+    assert_eq!(used.len(), positions.len(), "a point is used twice");
+    for i in 0..n as u32 {
+        assert!(
+            positions.contains(&key(point(i))),
+            "point {i} is missing from the triangulation"
+        );
+    }
+
+    if d.triangles.is_empty() {
+        // Collinear points: the hull lists them all along the line
+        assert_eq!(d.hull.len(), used.len());
+        return;
+    }
+
+    // Euler's formula for a triangulation of the convex hull
+    assert_eq!(d.triangles.len() / 3, 2 * used.len() - d.hull.len() - 2);
+
+    // The triangles cover exactly the hull
+    let area: f64 = d
+        .triangles
+        .chunks_exact(3)
+        .map(|t| {
+            let [a, b, c] = [t[0], t[1], t[2]].map(|i| coord(point(i)));
+            orient2d(a, b, c).abs() / 2.0
+        })
+        .sum();
+    // Fan from the first hull point rather than the shoelace formula, which
+    // loses all precision far from the origin
+    let first = coord(point(d.hull[0]));
+    let expected: f64 = d
+        .hull
+        .windows(2)
+        .map(|e| orient2d(first, coord(point(e[0])), coord(point(e[1]))).abs() / 2.0)
+        .sum();
+    assert!(
+        (area - expected).abs() <= 1e-9 * expected,
+        "triangles cover {area} but the hull encloses {expected}"
+    );
+
+    // The hull corners match an independent convex hull, compared by
+    // position since either may pick any of a set of duplicates
+    let h = d.hull.len();
+    let corners: Vec<(u64, u64)> = (0..h)
+        .filter(|&k| {
+            let [a, b, c] =
+                [d.hull[(k + h - 1) % h], d.hull[k], d.hull[(k + 1) % h]].map(|i| coord(point(i)));
+            orient2d(a, b, c) != 0.0
+        })
+        .map(|k| key(point(d.hull[k])))
+        .collect();
+    let reference: Vec<(u64, u64)> = convex_hull(coords)
+        .into_iter()
+        .map(|i| key(point(i)))
+        .collect();
+    assert_eq!(rotated(&corners), rotated(&reference));
+}
+
+// Exact position of a point, with -0.0 and 0.0 made equal
+fn key((x, y): (f64, f64)) -> (u64, u64) {
+    ((x + 0.0).to_bits(), (y + 0.0).to_bits())
+}
+
+fn coord((x, y): (f64, f64)) -> Coord<f64> {
+    Coord { x, y }
+}
+
+// Rotates a cycle to start at its smallest index, for comparison
+fn rotated<T: Ord + Copy>(cycle: &[T]) -> Vec<T> {
+    let start = (0..cycle.len()).min_by_key(|&k| cycle[k]).unwrap_or(0);
+    cycle[start..]
+        .iter()
+        .chain(&cycle[..start])
+        .copied()
+        .collect()
+}
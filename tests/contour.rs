@@ -0,0 +1,162 @@
+//! Isolines and filled contours of cones, whose level lines are circles
+
+use std::f64::consts::PI;
+
+use delaunator_rs::Delaunator;
+
+const SIDES: usize = 24;
+const RINGS: usize = 4;
+
+// Center and concentric rings of radius 1 to RINGS, with the ring number
+// as value, so that level k goes exactly through the points of ring k
+fn cone() -> (Delaunator, Vec<f64>) {
+    let mut coords = vec![0.0, 0.0];
+    let mut values = vec![0.0];
+    for k in 1..=RINGS {
+        for i in 0..SIDES {
+            let angle = 2.0 * PI * i as f64 / SIDES as f64;
+            coords.extend([k as f64 * angle.cos(), k as f64 * angle.sin()]);
+            values.push(k as f64);
+        }
+    }
+    (Delaunator::new(coords).unwrap(), values)
+}
+
+fn points(coords: &[f64]) -> impl Iterator<Item = (f64, f64)> + '_ {
+    coords.chunks_exact(2).map(|p| (p[0], p[1]))
+}
+
+fn signed_area(coords: &[f64]) -> f64 {
+    let p: Vec<(f64, f64)> = points(coords).collect();
+    (0..p.len())
+        .map(|i| {
+            let (a, b) = (p[i], p[(i + 1) % p.len()]);
+            a.0 * b.1 - b.0 * a.1
+        })
+        .sum::<f64>()
+        / 2.0
+}
+
+// Area of the regular polygon inscribed in the circle of radius r
+fn polygon_area(r: f64) -> f64 {
+    SIDES as f64 / 2.0 * r * r * (2.0 * PI / SIDES as f64).sin()
+}
+
+#[test]
+fn closed_circles() {
+    let (d, values) = cone();
+    let contours = d.contours(&values, &[0.5, 1.5, 2.5, 3.5]);
+    assert_eq!(contours.len(), 4);
+
+    for contour in &contours {
+        assert_eq!(contour.lines.len(), 1, "level {}", contour.level);
+        let line = &contour.lines[0];
+        assert!(line.closed);
+
+        // Crossings of the edges between two rings are within the width of
+        // a chord of the circle through the level
+        for (x, y) in points(&line.coords) {
+            let r = x.hypot(y);
+            assert!((r - contour.level).abs() < 0.05 * contour.level, "{r}");
+        }
+
+        // Higher values on the right: counterclockwise around the apex
+        assert!(signed_area(&line.coords) > 0.0);
+    }
+}
+
+#[test]
+fn open_arcs() {
+    // Cone centered on a hull point, so that its circles leave the hull
+    let (d, _) = cone();
+    let apex = (RINGS as f64, 0.0);
+    let values: Vec<f64> = points(d.coords())
+        .map(|(x, y)| (x - apex.0).hypot(y - apex.1))
+        .collect();
+
+    let contours = d.contours(&values, &[1.5, 3.0, 5.0]);
+    for contour in &contours {
+        assert_eq!(contour.lines.len(), 1, "level {}", contour.level);
+        let line = &contour.lines[0];
+        assert!(!line.closed);
+
+        for (x, y) in points(&line.coords) {
+            let r = (x - apex.0).hypot(y - apex.1);
+            assert!((r - contour.level).abs() < 0.05 * contour.level, "{r}");
+        }
+
+        // Both ends are on the hull polygon
+        let n = line.coords.len();
+        let inner = RINGS as f64 * (PI / SIDES as f64).cos();
+        for (x, y) in [
+            (line.coords[0], line.coords[1]),
+            (line.coords[n - 2], line.coords[n - 1]),
+        ] {
+            assert!(x.hypot(y) >= inner - 1e-9);
+        }
+    }
+}
+
+#[test]
+fn level_through_points() {
+    // Level 2 crosses edges exactly at the points of ring 2
+    let (d, values) = cone();
+    let contours = d.contours(&values, &[2.0]);
+    assert_eq!(contours[0].lines.len(), 1);
+    let line = &contours[0].lines[0];
+    assert!(line.closed);
+    assert_eq!(line.coords.len(), 2 * SIDES);
+    for (x, y) in points(&line.coords) {
+        assert!((x.hypot(y) - 2.0).abs() < 1e-12);
+    }
+    assert!((signed_area(&line.coords) - polygon_area(2.0)).abs() < 1e-9);
+
+    // The band between levels 1 and 2 is bounded by rings 1 and 2
+    let bands = d.filled_contours(&values, &[0.0, 1.0, 2.0, RINGS as f64]);
+    assert_eq!(bands.len(), 3);
+    let band = &bands[1];
+    assert_eq!(band.rings.len(), 2);
+    let mut areas: Vec<f64> = band.rings.iter().map(|ring| signed_area(ring)).collect();
+    areas.sort_by(|a, b| b.abs().total_cmp(&a.abs()));
+    for ring in &band.rings {
+        assert_eq!(ring.len(), 2 * SIDES);
+    }
+
+    // The outer ring winds like the hull, the hole the other way
+    let hull: Vec<f64> = d
+        .hull
+        .iter()
+        .flat_map(|&i| [d.coords()[2 * i as usize], d.coords()[2 * i as usize + 1]])
+        .collect();
+    let hull_sign = signed_area(&hull).signum();
+    assert!((areas[0] - hull_sign * polygon_area(2.0)).abs() < 1e-9);
+    assert!((areas[1] + hull_sign * polygon_area(1.0)).abs() < 1e-9);
+
+    // The last band includes its upper level, so the outermost ring
+    // belongs to it alone
+    let last: f64 = bands[2].rings.iter().map(|ring| signed_area(ring)).sum();
+    let expected = polygon_area(RINGS as f64) - polygon_area(2.0);
+    assert!((last - hull_sign * expected).abs() < 1e-9);
+}
+
+#[test]
+fn flat_triangle() {
+    let d = Delaunator::new(vec![0.0, 0.0, 2.0, 0.0, 0.0, 2.0]).unwrap();
+    let values = [1.0, 1.0, 1.0];
+
+    // Every point is above the level, so no line crosses the triangle
+    let contours = d.contours(&values, &[0.5, 1.0, 1.5]);
+    assert!(contours.iter().all(|c| c.lines.is_empty()));
+
+    // The triangle is in the band that starts at its value
+    let bands = d.filled_contours(&values, &[0.0, 1.0, 2.0]);
+    assert!(bands[0].rings.is_empty());
+    assert_eq!(bands[1].rings.len(), 1);
+    let ring = &bands[1].rings[0];
+    assert_eq!(ring.len(), 6);
+    assert!((signed_area(ring).abs() - 2.0).abs() < 1e-12);
+
+    // As the upper level of the last band, it belongs to that band
+    let bands = d.filled_contours(&values, &[0.0, 1.0]);
+    assert_eq!(bands[0].rings.len(), 1);
+}
@@ -0,0 +1,280 @@
+//! Tetrahedralizations checked against every point
+
+use std::collections::HashSet;
+
+use delaunator_rs::delaunay3d::Delaunay3d;
+use delaunator_rs::Error;
+use proptest::prelude::*;
+use robust::{insphere, orient3d, Coord3D};
+
+fn flatten(points: Vec<(f64, f64, f64)>) -> Vec<f64> {
+    points.into_iter().flat_map(|(x, y, z)| [x, y, z]).collect()
+}
+
+fn random() -> impl Strategy<Value = Vec<f64>> {
+    prop::collection::vec((-1e3..1e3, -1e3..1e3, -1e3..1e3), 4..100).prop_map(flatten)
+}
+
+// Corners of unit cubes, whose 8 corners are cospherical
+fn cube_corners() -> impl Strategy<Value = Vec<f64>> {
+    (2..6usize, 2..6usize, 2..6usize).prop_map(|(w, h, d)| {
+        let mut coords = Vec::with_capacity(3 * w * h * d);
+        for i in 0..w {
+            for j in 0..h {
+                for k in 0..d {
+                    coords.extend([i as f64, j as f64, k as f64]);
+                }
+            }
+        }
+        coords
+    })
+}
+
+// Lattice points of the sphere of radius 7, all of them cospherical,
+// optionally with the center
+fn cospherical() -> impl Strategy<Value = Vec<f64>> {
+    let r = 7;
+    let lattice: Vec<(f64, f64, f64)> = (-r..=r)
+        .flat_map(|x| (-r..=r).flat_map(move |y| (-r..=r).map(move |z| (x, y, z))))
+        .filter(|&(x, y, z)| x * x + y * y + z * z == r * r)
+        .map(|(x, y, z)| (x as f64, y as f64, z as f64))
+        .collect();
+    (
+        prop::sample::subsequence(lattice.clone(), 4..=lattice.len()),
+        any::<bool>(),
+    )
+        .prop_map(|(points, center)| {
+            let mut coords = flatten(points);
+            if center {
+                coords.extend([0.0, 0.0, 0.0]);
+            }
+            coords
+        })
+}
+
+// Points on the plane x + 2y - z = 3, which give no tetrahedra
+fn coplanar() -> impl Strategy<Value = Vec<f64>> {
+    prop::collection::vec((-100..100i32, -100..100i32), 4..50).prop_map(|points| {
+        points
+            .into_iter()
+            .flat_map(|(x, y)| [x as f64, y as f64, (x + 2 * y - 3) as f64])
+            .collect()
+    })
+}
+
+// Few distinct points, each repeated
+fn duplicates() -> impl Strategy<Value = Vec<f64>> {
+    prop::collection::vec((-1e3..1e3, -1e3..1e3, -1e3..1e3), 4..10).prop_flat_map(|points| {
+        prop::collection::vec(prop::sample::select(points), 10..100).prop_map(flatten)
+    })
+}
+
+fn point(coords: &[f64], i: u32) -> Coord3D<f64> {
+    let i = i as usize;
+    Coord3D {
+        x: coords[3 * i],
+        y: coords[3 * i + 1],
+        z: coords[3 * i + 2],
+    }
+}
+
+// Checks the twins, the orientation and empty sphere of every tetrahedron,
+// the hull and the counts of the tetrahedralization
+fn check(d: &Delaunay3d, coords: &[f64]) {
+    let n = coords.len() / 3;
+    assert_eq!(d.tetrahedra.len(), d.halffaces.len());
+    assert_eq!(d.tetrahedra.len() % 4, 0);
+
+    // Twins point back to each other and join the same face reversed
+    let mut boundary = 0;
+    for f in 0..d.halffaces.len() {
+        let twin = d.halffaces[f];
+        if twin == -1 {
+            boundary += 1;
+            continue;
+        }
+        let twin = twin as usize;
+        assert_eq!(d.halffaces[twin], f as i32, "halfface {f} is not symmetric");
+
+        let [a, b, c] = d.face(f);
+        let reversed = [[a, c, b], [c, b, a], [b, a, c]];
+        assert!(
+            reversed.contains(&d.face(twin)),
+            "halfface {f} and its twin {twin} do not join the same face"
+        );
+    }
+    assert_eq!(d.hull.len(), 3 * boundary);
+
+    // Positive orientation and empty circumsphere
+    let mut volume = 0.0;
+    for (t, corners) in d.tetrahedra.chunks_exact(4).enumerate() {
+        let [a, b, c, e] = [0, 1, 2, 3].map(|k| point(coords, corners[k]));
+        let orientation = orient3d(a, b, c, e);
+        assert!(orientation > 0.0, "tetrahedron {t} is flat or inverted");
+        volume += orientation / 6.0;
+
+        for p in 0..n as u32 {
+            assert!(
+                insphere(a, b, c, e, point(coords, p)) <= 0.0,
+                "point {p} is inside the circumsphere of tetrahedron {t}"
+            );
+        }
+    }
+
+    if d.tetrahedra.is_empty() {
+        assert!(d.hull.is_empty());
+        return;
+    }
+
+    // Every point lies on the inner side of every hull face, and the
+    // tetrahedra fill the volume enclosed by the hull
+    let origin = point(coords, d.hull[0]);
+    let mut enclosed = 0.0;
+    for face in d.hull.chunks_exact(3) {
+        let [a, b, c] = [face[0], face[1], face[2]].map(|i| point(coords, i));
+        for p in 0..n as u32 {
+            assert!(
+                orient3d(a, b, c, point(coords, p)) >= 0.0,
+                "point {p} is outside hull face {face:?}"
+            );
+        }
+        enclosed += orient3d(a, b, c, origin) / 6.0;
+    }
+    assert!(
+        (volume - enclosed).abs() <= 1e-9 * enclosed,
+        "tetrahedra fill {volume} but the hull encloses {enclosed}"
+    );
+
+    // Every point is used, except duplicates
+    let used: HashSet<u32> = d.tetrahedra.iter().copied().collect();
+    let key = |i: u32| {
+        let p = point(coords, i);
+        [p.x, p.y, p.z].map(|v| (v + 0.0).to_bits())
+    };
+    let positions: HashSet<[u64; 3]> = used.iter().map(|&i| key(i)).collect();
+    assert_eq!(used.len(), positions.len(), "a point is used twice");
+    for i in 0..n as u32 {
+        assert!(positions.contains(&key(i)), "point {i} is missing");
+    }
+
+    // Euler's formula for a ball, V - E + F - T = 1
+    let mut edges = HashSet::new();
+    for corners in d.tetrahedra.chunks_exact(4) {
+        for j in 0..4 {
+            for k in j + 1..4 {
+                let (u, v) = (corners[j], corners[k]);
+                edges.insert((u.min(v), u.max(v)));
+            }
+        }
+    }
+    let tetrahedra = d.tetrahedra.len() / 4;
+    let faces = (4 * tetrahedra + boundary) / 2;
+    assert_eq!(
+        used.len() as i64 - edges.len() as i64 + faces as i64 - tetrahedra as i64,
+        1
+    );
+}
+
+fn tetrahedralize(coords: &[f64]) -> Delaunay3d {
+    let d = Delaunay3d::new(coords.to_vec()).expect("valid coordinates");
+    check(&d, coords);
+    d
+}
+
+proptest! {
+    #[test]
+    fn random_points(coords in random()) {
+        tetrahedralize(&coords);
+    }
+
+    #[test]
+    fn cube_corner_points(coords in cube_corners()) {
+        let d = tetrahedralize(&coords);
+        prop_assert!(!d.tetrahedra.is_empty());
+    }
+
+    #[test]
+    fn cospherical_points(coords in cospherical()) {
+        tetrahedralize(&coords);
+    }
+
+    #[test]
+    fn coplanar_points(coords in coplanar()) {
+        let d = tetrahedralize(&coords);
+        prop_assert!(d.tetrahedra.is_empty());
+    }
+
+    #[test]
+    fn duplicate_points(coords in duplicates()) {
+        tetrahedralize(&coords);
+    }
+}
+
+#[test]
+fn single_cube() {
+    // The 8 corners split into 5 or 6 tetrahedra, depending on the diagonals
+    let coords = vec![
+        0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0, 0.0, 1.0, 1.0, 0.0, 0.0, 0.0, 1.0, 1.0, 0.0, 1.0,
+        0.0, 1.0, 1.0, 1.0, 1.0, 1.0,
+    ];
+    let d = tetrahedralize(&coords);
+    assert!([5, 6].contains(&(d.tetrahedra.len() / 4)));
+    assert_eq!(d.hull.len() / 3, 12);
+}
+
+#[test]
+fn repeated_update() {
+    let coords: Vec<f64> = (0..60).map(|i| ((i * 37) % 23) as f64).collect();
+    let mut d = tetrahedralize(&coords);
+    let (tetrahedra, halffaces, hull) = (d.tetrahedra.clone(), d.halffaces.clone(), d.hull.clone());
+    d.update().unwrap();
+    assert_eq!(d.tetrahedra, tetrahedra);
+    assert_eq!(d.halffaces, halffaces);
+    assert_eq!(d.hull, hull);
+}
+
+// Scaling by powers of two is exact, so it must not change the result, down
+// to where the in-sphere test would overflow or underflow
+#[test]
+fn scaled_points() {
+    let coords: Vec<f64> = (0..300).map(|i| ((i * 37) % 101) as f64).collect();
+    let d = tetrahedralize(&coords);
+    for scale in [2f64.powi(-180), 2f64.powi(180)] {
+        let scaled: Vec<f64> = coords.iter().map(|v| v * scale).collect();
+        let s = tetrahedralize(&scaled);
+        assert_eq!(s.tetrahedra, d.tetrahedra);
+        assert_eq!(s.halffaces, d.halffaces);
+        assert_eq!(s.hull, d.hull);
+    }
+
+    for scale in [1e-70, 1e70] {
+        let scaled: Vec<f64> = coords.iter().map(|v| v * scale).collect();
+        assert_eq!(Delaunay3d::new(scaled).unwrap_err(), Error::CoordinateRange);
+    }
+
+    // A single point repeated has no spread at all
+    assert!(Delaunay3d::new(vec![1e300; 12])
+        .unwrap()
+        .tetrahedra
+        .is_empty());
+}
+
+#[test]
+fn invalid_input() {
+    assert_eq!(
+        Delaunay3d::new(vec![]).unwrap_err(),
+        Error::InvalidCoordinates
+    );
+    assert_eq!(
+        Delaunay3d::new(vec![0.0, 0.0]).unwrap_err(),
+        Error::InvalidCoordinates
+    );
+    assert_eq!(
+        Delaunay3d::new(vec![0.0, 0.0, f64::NAN]).unwrap_err(),
+        Error::NonFiniteCoordinate
+    );
+    assert_eq!(
+        Delaunay3d::new(vec![-1e300, 0.0, 0.0, 1e300, 0.0, 0.0]).unwrap_err(),
+        Error::CoordinateRange
+    );
+}
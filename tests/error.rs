@@ -0,0 +1,42 @@
+//! Errors of the constructors on invalid input
+
+use delaunator_rs::{Delaunator, Error, Triangulation};
+
+#[test]
+fn invalid_input() {
+    assert_eq!(
+        Delaunator::new(vec![0.0, 0.0, 1.0]).unwrap_err(),
+        Error::InvalidCoordinates
+    );
+    assert_eq!(
+        Delaunator::new(vec![0.0, 0.0, 1.0, f64::NAN, 0.0, 1.0]).unwrap_err(),
+        Error::NonFiniteCoordinate
+    );
+    assert_eq!(
+        Delaunator::new(vec![0.0, 0.0, 1e300, 0.0, 0.0, 1.0]).unwrap_err(),
+        Error::CoordinateRange
+    );
+    assert_eq!(
+        Delaunator::with_weights(vec![0.0, 0.0, 1.0, 0.0], vec![1.0]).unwrap_err(),
+        Error::InvalidWeights
+    );
+    assert_eq!(
+        Error::InvalidCoordinates.to_string(),
+        "Invalid coordinates array"
+    );
+}
+
+#[test]
+fn too_many_points() {
+    // 10926 points may need 65541 halfedges, more than u16 can index below
+    // its NONE marker
+    let coords: Vec<f64> = (0..10926)
+        .flat_map(|i| [i as f64, (i * i % 7) as f64])
+        .collect();
+    assert!(Triangulation::<u16>::new(coords[..2 * 10925].to_vec()).is_ok());
+    assert_eq!(
+        Triangulation::<u16>::new(coords.clone()).unwrap_err(),
+        Error::TooManyPoints
+    );
+    assert!(Triangulation::<u32>::new(coords).is_ok());
+}
@@ -0,0 +1,174 @@
+//! Fixed inputs with known hulls and triangle counts, starting with the
+//! test cases of the JavaScript delaunator suite
+
+mod common;
+
+use delaunator_rs::Delaunator;
+
+const ISSUE_11: [[f64; 2]; 7] = [
+    [516.0, 661.0],
+    [369.0, 793.0],
+    [426.0, 539.0],
+    [273.0, 525.0],
+    [204.0, 694.0],
+    [747.0, 750.0],
+    [454.0, 390.0],
+];
+
+// Many points on a vertical line, and one in the middle of a hull edge
+const ISSUE_24: [[f64; 2]; 9] = [
+    [382.0, 302.0],
+    [382.0, 328.0],
+    [382.0, 205.0],
+    [623.0, 175.0],
+    [382.0, 188.0],
+    [382.0, 284.0],
+    [623.0, 87.0],
+    [623.0, 341.0],
+    [141.0, 227.0],
+];
+
+fn flatten(points: &[[f64; 2]]) -> Vec<f64> {
+    points.iter().flatten().copied().collect()
+}
+
+// Same cycle up to the starting point
+fn assert_same_cycle(actual: &[u32], expected: &[u32]) {
+    let start = actual.iter().position(|&i| i == expected[0]);
+    let rotated: Option<Vec<u32>> =
+        start.map(|s| actual[s..].iter().chain(&actual[..s]).copied().collect());
+    assert_eq!(rotated.as_deref(), Some(expected), "hull {actual:?}");
+}
+
+#[test]
+fn issue_11() {
+    let coords = flatten(&ISSUE_11);
+    let d = common::triangulate(&coords);
+    assert_eq!(d.triangles.len(), 21);
+    assert_same_cycle(&d.hull, &[1, 5, 6, 3, 4]);
+}
+
+#[test]
+fn issue_24() {
+    let coords = flatten(&ISSUE_24);
+    let d = common::triangulate(&coords);
+    assert_eq!(d.triangles.len(), 33);
+    assert_same_cycle(&d.hull, &[6, 8, 1, 7, 3]);
+}
+
+// The robustness test of the JavaScript suite runs its fixtures scaled by
+// these factors, which the circle test must cope with
+#[test]
+fn scaled_upstream_cases() {
+    for (points, triangles) in [(&ISSUE_11[..], 21), (&ISSUE_24[..], 33)] {
+        for scale in [1e-9, 1e-2, 1e2, 1e9] {
+            let coords: Vec<f64> = flatten(points).iter().map(|v| v * scale).collect();
+            let d = common::triangulate(&coords);
+            assert_eq!(d.triangles.len(), triangles, "scaled by {scale}");
+        }
+    }
+}
+
+#[test]
+fn legalize_flips_illegal_edges() {
+    // legalize compared the wrong points and left an illegal edge here
+    let coords = [0.2, 5.6, 6.9, 5.8, 0.7, 8.7, 8.8, 7.6, 1.3, 4.0];
+    let d = common::triangulate(&coords);
+    assert_eq!(d.triangles.len(), 9);
+}
+
+#[test]
+fn point_on_hull_edge() {
+    // Point 0 is on the hull edge from 1 to 2 up to rounding, and the
+    // inexact orientation test took it for a point outside of the edge
+    let coords = [
+        0.3128469311400607,
+        69.89158347201978,
+        0.1865748715879239,
+        69.71119946176044,
+        0.691663109796471,
+        70.43273550279777,
+        1.2662850567904358,
+        69.85344155291213,
+    ];
+    let d = common::triangulate(&coords);
+    assert_eq!(d.triangles.len(), 6);
+    assert_same_cycle(&d.hull, &[3, 1, 0, 2]);
+}
+
+#[test]
+fn all_collinear() {
+    let coords = flatten(&[[0.0, 0.0], [1.0, 0.0], [3.0, 0.0], [2.0, 0.0]]);
+    let d = common::triangulate(&coords);
+    assert!(d.triangles.is_empty());
+    assert_eq!(d.hull, [0, 1, 3, 2]);
+}
+
+#[test]
+fn collinear_with_duplicates() {
+    let coords = flatten(&[[0.0, 0.0], [2.0, 2.0], [1.0, 1.0], [2.0, 2.0], [0.0, 0.0]]);
+    let d = common::triangulate(&coords);
+    assert!(d.triangles.is_empty());
+    assert_eq!(d.hull.len(), 3);
+}
+
+#[test]
+fn single_triangle() {
+    let d = common::triangulate(&[0.0, 0.0, 1.0, 0.0, 0.0, 1.0]);
+    assert_eq!(d.triangles.len(), 3);
+    assert_eq!(d.halfedges, [-1, -1, -1]);
+}
+
+#[test]
+fn square_grid() {
+    // Every cell is cocircular, so any diagonal is valid
+    let coords: Vec<f64> = (0..10)
+        .flat_map(|i| (0..10).flat_map(move |j| [i as f64, j as f64]))
+        .collect();
+    let d = common::triangulate(&coords);
+    assert_eq!(d.triangles.len() / 3, 2 * 9 * 9);
+    assert_eq!(d.hull.len(), 36);
+}
+
+#[test]
+fn repeated_update() {
+    let coords: Vec<f64> = (0..100)
+        .flat_map(|i| {
+            let i = i as f64;
+            [(i * 7.3) % 13.0, (i * 3.1) % 11.0]
+        })
+        .collect();
+    let mut d = common::triangulate(&coords);
+    let (triangles, hull) = (d.triangles.clone(), d.hull.clone());
+    d.update().unwrap();
+    common::check(&d, &coords);
+    assert_eq!(d.triangles, triangles);
+    assert_eq!(d.hull, hull);
+}
+
+#[test]
+fn rounded_grid() {
+    // The first grid to fail once the hull hash had ceil(sqrt(n)) buckets,
+    // as its spacing rounds and the sweep met the inexact orientation test
+    let spacing = 1.582924024901558;
+    let coords: Vec<f64> = (0..16)
+        .flat_map(|i| (0..13).flat_map(move |j| [i as f64 * spacing, j as f64 * spacing]))
+        .collect();
+    let d = common::triangulate(&coords);
+    assert_eq!(d.triangles.len() / 3, 2 * 15 * 12);
+    assert_eq!(d.hull.len(), 2 * (15 + 12));
+}
+
+#[test]
+fn fewer_than_three_points() {
+    for (coords, hull) in [
+        (vec![], vec![]),
+        (vec![5.0, 5.0], vec![0]),
+        (vec![5.0, 5.0, 1.0, 2.0], vec![0, 1]),
+    ] {
+        let d = Delaunator::new(coords).unwrap();
+        assert!(d.triangles.is_empty());
+        assert!(d.halfedges.is_empty());
+        assert_eq!(d.hull, hull);
+    }
+}
@@ -0,0 +1,247 @@
+//! Proximity graphs against their definitions over all pairs of points
+
+use std::collections::BTreeSet;
+
+use delaunator_rs::Delaunator;
+use proptest::prelude::*;
+
+// Points in general position: no exactly equal distances or angles
+fn random() -> impl Strategy<Value = Vec<f64>> {
+    prop::collection::vec((-100.0..100.0, -100.0..100.0), 3..60)
+        .prop_map(|points| points.into_iter().flat_map(|(x, y)| [x, y]).collect())
+}
+
+// Integer steps along an integer direction, so exactly collinear, with
+// repeated steps giving duplicates
+fn collinear() -> impl Strategy<Value = Vec<f64>> {
+    (
+        (-10..10i32, -10..10i32).prop_filter("nonzero direction", |&d| d != (0, 0)),
+        prop::collection::vec(-20..20i32, 2..30),
+    )
+        .prop_map(|((dx, dy), steps)| {
+            steps
+                .into_iter()
+                .flat_map(|t| [(7 + t * dx) as f64, (-3 + t * dy) as f64])
+                .collect()
+        })
+}
+
+// Few distinct points, each repeated
+fn duplicates() -> impl Strategy<Value = Vec<f64>> {
+    prop::collection::vec((-100.0..100.0, -100.0..100.0), 3..10).prop_flat_map(|points| {
+        prop::collection::vec(prop::sample::select(points), 3..40)
+            .prop_map(|points| points.into_iter().flat_map(|(x, y)| [x, y]).collect())
+    })
+}
+
+// Distinct points, in order of first appearance
+fn distinct(coords: &[f64]) -> Vec<f64> {
+    let mut seen = BTreeSet::new();
+    coords
+        .chunks_exact(2)
+        .filter(|p| seen.insert((p[0].to_bits(), p[1].to_bits())))
+        .flatten()
+        .copied()
+        .collect()
+}
+
+// Edges as sorted pairs of positions, checking that no position has edges
+// through two different points
+fn position_set(coords: &[f64], edges: &[u32]) -> BTreeSet<((u64, u64), (u64, u64))> {
+    let key = |i: u32| {
+        let (x, y) = point(coords, i as usize);
+        (x.to_bits(), y.to_bits())
+    };
+    let points: BTreeSet<u32> = edges.iter().copied().collect();
+    let positions: BTreeSet<(u64, u64)> = points.iter().map(|&i| key(i)).collect();
+    assert_eq!(points.len(), positions.len(), "duplicate point with edges");
+    edge_set(edges)
+        .into_iter()
+        .map(|(a, b)| (key(a).min(key(b)), key(a).max(key(b))))
+        .collect()
+}
+
+// Checks every graph against brute force over the distinct points
+fn check_positions(coords: &[f64]) {
+    let d = Delaunator::new(coords.to_vec()).unwrap();
+    let unique = distinct(coords);
+    let brute = |edges: BTreeSet<(u32, u32)>| {
+        let flat: Vec<u32> = edges.into_iter().flat_map(|(a, b)| [a, b]).collect();
+        position_set(&unique, &flat)
+    };
+
+    let gabriel = brute(brute_gabriel(&unique));
+    assert_eq!(position_set(coords, &d.gabriel_graph()), gabriel);
+    assert_eq!(
+        position_set(coords, &d.relative_neighborhood_graph()),
+        brute(brute_rng(&unique))
+    );
+    let urquhart = position_set(coords, &d.urquhart_graph());
+    assert!(urquhart.is_subset(&gabriel));
+    assert!(gabriel.is_subset(&position_set(coords, &d.edges())));
+
+    let mst = d.euclidean_mst();
+    position_set(coords, &mst);
+    assert_eq!(mst.len() / 2, unique.len() / 2 - 1);
+    let expected = prim_weight(&unique);
+    assert!((weight(coords, &mst) - expected).abs() <= 1e-9 * expected);
+}
+
+fn point(coords: &[f64], i: usize) -> (f64, f64) {
+    (coords[2 * i], coords[2 * i + 1])
+}
+
+fn dist2(a: (f64, f64), b: (f64, f64)) -> f64 {
+    (a.0 - b.0).powi(2) + (a.1 - b.1).powi(2)
+}
+
+// Edges as sorted pairs, checking that none appears twice
+fn edge_set(edges: &[u32]) -> BTreeSet<(u32, u32)> {
+    let set: BTreeSet<(u32, u32)> = edges
+        .chunks_exact(2)
+        .map(|e| (e[0].min(e[1]), e[0].max(e[1])))
+        .collect();
+    assert_eq!(set.len(), edges.len() / 2, "repeated edge");
+    set
+}
+
+fn weight(coords: &[f64], edges: &[u32]) -> f64 {
+    edges
+        .chunks_exact(2)
+        .map(|e| dist2(point(coords, e[0] as usize), point(coords, e[1] as usize)).sqrt())
+        .sum()
+}
+
+// Prim's algorithm over the complete graph
+fn prim_weight(coords: &[f64]) -> f64 {
+    let n = coords.len() / 2;
+    let mut in_tree = vec![false; n];
+    let mut best = vec![f64::INFINITY; n];
+    best[0] = 0.0;
+    let mut total = 0.0;
+    for _ in 0..n {
+        let v = (0..n)
+            .filter(|&v| !in_tree[v])
+            .min_by(|&a, &b| best[a].total_cmp(&best[b]))
+            .unwrap();
+        in_tree[v] = true;
+        total += best[v].sqrt();
+        for w in 0..n {
+            if !in_tree[w] {
+                best[w] = best[w].min(dist2(point(coords, v), point(coords, w)));
+            }
+        }
+    }
+    total
+}
+
+// Pairs whose diametral circle holds no other point
+fn brute_gabriel(coords: &[f64]) -> BTreeSet<(u32, u32)> {
+    let n = coords.len() / 2;
+    let mut edges = BTreeSet::new();
+    for p in 0..n {
+        for q in p + 1..n {
+            let (a, b) = (point(coords, p), point(coords, q));
+            let center = ((a.0 + b.0) / 2.0, (a.1 + b.1) / 2.0);
+            let r2 = dist2(a, b) / 4.0;
+            if (0..n).all(|r| r == p || r == q || dist2(center, point(coords, r)) >= r2) {
+                edges.insert((p as u32, q as u32));
+            }
+        }
+    }
+    edges
+}
+
+// Pairs with no point closer to both ends than they are to each other
+fn brute_rng(coords: &[f64]) -> BTreeSet<(u32, u32)> {
+    let n = coords.len() / 2;
+    let mut edges = BTreeSet::new();
+    for p in 0..n {
+        for q in p + 1..n {
+            let (a, b) = (point(coords, p), point(coords, q));
+            let d = dist2(a, b);
+            let blocked = (0..n).any(|r| {
+                let c = point(coords, r);
+                r != p && r != q && dist2(a, c) < d && dist2(b, c) < d
+            });
+            if !blocked {
+                edges.insert((p as u32, q as u32));
+            }
+        }
+    }
+    edges
+}
+
+proptest! {
+    #[test]
+    fn subgraph_chain(coords in random()) {
+        let d = Delaunator::new(coords.clone()).unwrap();
+        let delaunay = edge_set(&d.edges());
+        let gabriel = edge_set(&d.gabriel_graph());
+        let urquhart = edge_set(&d.urquhart_graph());
+        let rng = edge_set(&d.relative_neighborhood_graph());
+        let emst = edge_set(&d.euclidean_mst());
+
+        prop_assert_eq!(emst.len(), coords.len() / 2 - 1);
+        prop_assert!(emst.is_subset(&rng));
+        prop_assert!(rng.is_subset(&urquhart));
+        prop_assert!(urquhart.is_subset(&gabriel));
+        prop_assert!(gabriel.is_subset(&delaunay));
+    }
+
+    #[test]
+    fn brute_force(coords in random()) {
+        let d = Delaunator::new(coords.clone()).unwrap();
+        prop_assert_eq!(edge_set(&d.gabriel_graph()), brute_gabriel(&coords));
+        prop_assert_eq!(edge_set(&d.relative_neighborhood_graph()), brute_rng(&coords));
+
+        let mst = d.euclidean_mst();
+        let expected = prim_weight(&coords);
+        prop_assert!((weight(&coords, &mst) - expected).abs() <= 1e-9 * expected);
+
+        // Kruskal adds the edges by increasing length
+        let lengths: Vec<f64> = mst
+            .chunks_exact(2)
+            .map(|e| dist2(point(&coords, e[0] as usize), point(&coords, e[1] as usize)))
+            .collect();
+        prop_assert!(lengths.windows(2).all(|w| w[0] <= w[1]));
+    }
+}
+
+proptest! {
+    #[test]
+    fn collinear_points(coords in collinear()) {
+        let d = Delaunator::new(coords.clone()).unwrap();
+        prop_assert!(d.triangles.is_empty());
+        check_positions(&coords);
+    }
+
+    #[test]
+    fn duplicate_points(coords in duplicates()) {
+        check_positions(&coords);
+    }
+}
+
+#[test]
+fn two_points() {
+    let d = Delaunator::new(vec![0.0, 0.0, 3.0, 4.0]).unwrap();
+    for edges in [
+        d.edges(),
+        d.euclidean_mst(),
+        d.gabriel_graph(),
+        d.relative_neighborhood_graph(),
+        d.urquhart_graph(),
+    ] {
+        assert_eq!(edge_set(&edges), BTreeSet::from([(0, 1)]));
+    }
+
+    // Coincident points, and a single point, have no edges
+    for coords in [vec![1.0, 2.0, 1.0, 2.0], vec![1.0, 2.0]] {
+        let d = Delaunator::new(coords).unwrap();
+        assert!(d.edges().is_empty());
+        assert!(d.euclidean_mst().is_empty());
+        assert!(d.gabriel_graph().is_empty());
+        assert!(d.relative_neighborhood_graph().is_empty());
+        assert!(d.urquhart_graph().is_empty());
+    }
+}
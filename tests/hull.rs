@@ -0,0 +1,201 @@
+//! Convex hulls and their measures checked against the triangulation
+
+mod common;
+
+use delaunator_rs::hull::{convex_hull, hull_area, hull_centroid, hull_perimeter};
+use proptest::prelude::*;
+use robust::{orient2d, Coord};
+
+fn flatten(points: Vec<(f64, f64)>) -> Vec<f64> {
+    points.into_iter().flat_map(|(x, y)| [x, y]).collect()
+}
+
+fn random() -> impl Strategy<Value = Vec<f64>> {
+    prop::collection::vec((-1e3..1e3, -1e3..1e3), 3..100).prop_map(flatten)
+}
+
+// Small integer points, with many duplicates and collinear triples
+fn lattice() -> impl Strategy<Value = Vec<f64>> {
+    prop::collection::vec((-4..4i32, -4..4i32), 1..30).prop_map(|points| {
+        flatten(
+            points
+                .into_iter()
+                .map(|(x, y)| (x as f64, y as f64))
+                .collect(),
+        )
+    })
+}
+
+// Integer steps along an integer direction, so exactly collinear
+fn collinear() -> impl Strategy<Value = Vec<f64>> {
+    (
+        (-10..10i32, -10..10i32).prop_filter("nonzero direction", |&d| d != (0, 0)),
+        prop::collection::vec(-20..20i32, 2..30),
+    )
+        .prop_map(|((dx, dy), steps)| {
+            steps
+                .into_iter()
+                .flat_map(|t| [(7 + t * dx) as f64, (-3 + t * dy) as f64])
+                .collect()
+        })
+}
+
+fn coord(coords: &[f64], i: u32) -> Coord<f64> {
+    Coord {
+        x: coords[2 * i as usize],
+        y: coords[2 * i as usize + 1],
+    }
+}
+
+// Checks that the hull has distinct corners only, winds like the hull of
+// the triangulation, starts from the leftmost point and leaves every point
+// on its inner side
+fn check(coords: &[f64], hull: &[u32]) {
+    let point = |i: u32| coord(coords, i);
+    let h = hull.len();
+    assert!(h >= 3);
+
+    let lowest = (0..coords.len() as u32 / 2)
+        .map(point)
+        .min_by(|a, b| a.x.total_cmp(&b.x).then(a.y.total_cmp(&b.y)))
+        .unwrap();
+    assert_eq!(point(hull[0]), lowest, "the hull starts elsewhere");
+
+    for k in 0..h {
+        let [a, b, c] = [hull[k], hull[(k + 1) % h], hull[(k + 2) % h]].map(point);
+        assert!(
+            orient2d(a, b, c) < 0.0,
+            "hull point {} is not a corner",
+            hull[(k + 1) % h]
+        );
+        for i in 0..coords.len() as u32 / 2 {
+            assert!(
+                orient2d(a, b, point(i)) <= 0.0,
+                "point {i} is outside of hull edge {k}"
+            );
+        }
+    }
+}
+
+// Area, centroid and perimeter summed over the triangles and hull edges of
+// the triangulation
+fn measures(coords: &[f64]) -> (f64, (f64, f64), f64) {
+    let d = common::triangulate(coords);
+    let (mut area, mut cx, mut cy) = (0.0, 0.0, 0.0);
+    for t in d.triangles.chunks_exact(3) {
+        let [a, b, c] = [t[0], t[1], t[2]].map(|i| coord(coords, i));
+        let part = orient2d(a, b, c).abs() / 2.0;
+        area += part;
+        cx += part * (a.x + b.x + c.x) / 3.0;
+        cy += part * (a.y + b.y + c.y) / 3.0;
+    }
+
+    let mut perimeter = 0.0;
+    for (e, &twin) in d.halfedges.iter().enumerate() {
+        if twin == -1 {
+            let next = if e % 3 == 2 { e - 2 } else { e + 1 };
+            let [a, b] = [d.triangles[e], d.triangles[next]].map(|i| coord(coords, i));
+            perimeter += (b.x - a.x).hypot(b.y - a.y);
+        }
+    }
+
+    (area, (cx / area, cy / area), perimeter)
+}
+
+fn close(a: f64, b: f64, scale: f64) -> bool {
+    (a - b).abs() <= 1e-9 * scale
+}
+
+proptest! {
+    #[test]
+    fn random_points(coords in random()) {
+        let hull = convex_hull(&coords);
+        check(&coords, &hull);
+
+        let (area, (cx, cy), perimeter) = measures(&coords);
+        let scale = 1e3;
+        prop_assert!(close(hull_area(&coords, &hull), area, scale * scale));
+        prop_assert!(close(hull_perimeter(&coords, &hull), perimeter, scale));
+        let centroid = hull_centroid(&coords, &hull);
+        prop_assert!(close(centroid.0, cx, scale) && close(centroid.1, cy, scale));
+    }
+
+    // Duplicates and points on hull edges are left out, and collinear
+    // points are checked apart
+    #[test]
+    fn lattice_points(coords in lattice()) {
+        let hull = convex_hull(&coords);
+        if hull_area(&coords, &hull) > 0.0 {
+            check(&coords, &hull);
+            let (area, (cx, cy), perimeter) = measures(&coords);
+            prop_assert!(close(hull_area(&coords, &hull), area, 1.0));
+            prop_assert!(close(hull_perimeter(&coords, &hull), perimeter, 1.0));
+            let centroid = hull_centroid(&coords, &hull);
+            prop_assert!(close(centroid.0, cx, 1.0) && close(centroid.1, cy, 1.0));
+        }
+    }
+
+    // Collinear points come back as distinct points ordered along the line,
+    // and the centroid falls back to the middle of the segment
+    #[test]
+    fn collinear_points(coords in collinear()) {
+        let hull = convex_hull(&coords);
+        let mut positions: Vec<(i64, i64)> = coords
+            .chunks_exact(2)
+            .map(|p| (p[0] as i64, p[1] as i64))
+            .collect();
+        positions.sort_unstable();
+        positions.dedup();
+        prop_assert_eq!(hull.len(), positions.len());
+
+        let along: Vec<f64> = hull.iter().map(|&i| coords[2 * i as usize]).collect();
+        let across: Vec<f64> = hull.iter().map(|&i| coords[2 * i as usize + 1]).collect();
+        prop_assert!(along.windows(2).all(|w| w[0] <= w[1]));
+        prop_assert!(along
+            .windows(2)
+            .zip(across.windows(2))
+            .all(|(x, y)| x[0] < x[1] || y[0] < y[1]));
+
+        let (first, last) = (coord(&coords, hull[0]), coord(&coords, hull[hull.len() - 1]));
+        let length = (last.x - first.x).hypot(last.y - first.y);
+        prop_assert_eq!(hull_area(&coords, &hull), 0.0);
+        prop_assert!(close(hull_perimeter(&coords, &hull), 2.0 * length, 1.0));
+        let centroid = hull_centroid(&coords, &hull);
+        prop_assert!(close(centroid.0, (first.x + last.x) / 2.0, 1.0));
+        prop_assert!(close(centroid.1, (first.y + last.y) / 2.0, 1.0));
+    }
+}
+
+#[test]
+fn square() {
+    let coords = [0.0, 0.0, 2.0, 0.0, 2.0, 2.0, 0.0, 2.0, 1.0, 1.0, 1.0, 0.0];
+    let hull = convex_hull(&coords);
+    assert_eq!(hull, [0, 3, 2, 1]);
+    assert_eq!(hull_area(&coords, &hull), 4.0);
+    assert_eq!(hull_perimeter(&coords, &hull), 8.0);
+    assert_eq!(hull_centroid(&coords, &hull), (1.0, 1.0));
+}
+
+#[test]
+fn few_points() {
+    assert!(convex_hull(&[]).is_empty());
+    assert!(hull_centroid(&[], &[]).0.is_nan());
+
+    let coords = [3.0, 4.0, 3.0, 4.0, 3.0, 4.0];
+    let hull = convex_hull(&coords);
+    assert_eq!(hull.len(), 1);
+    assert_eq!(hull_area(&coords, &hull), 0.0);
+    assert_eq!(hull_perimeter(&coords, &hull), 0.0);
+    assert_eq!(hull_centroid(&coords, &hull), (3.0, 4.0));
+}
+
+// Far from the origin, absolute products would round away the whole area
+#[test]
+fn large_offset() {
+    let (x, y) = (1e9, -3e9);
+    let coords = [x, y, x + 2.0, y, x + 2.0, y + 1.0, x, y + 1.0];
+    let hull = convex_hull(&coords);
+    assert_eq!(hull_area(&coords, &hull), 2.0);
+    assert_eq!(hull_perimeter(&coords, &hull), 6.0);
+    assert_eq!(hull_centroid(&coords, &hull), (x + 1.0, y + 0.5));
+}
@@ -0,0 +1,58 @@
+//! Triangulations rebuilt from their arrays, and the arrays rejected
+
+mod common;
+
+use delaunator_rs::{Delaunator, Error};
+
+#[test]
+fn from_parts() {
+    let coords = vec![0.0, 0.0, 1.0, 0.0, 1.0, 1.0, 0.0, 1.0, 0.4, 0.6];
+    let d = common::triangulate(&coords);
+    let parts = || {
+        (
+            coords.clone(),
+            d.triangles.clone(),
+            d.halfedges.clone(),
+            d.hull.clone(),
+        )
+    };
+
+    let (c, t, h, hull) = parts();
+    let rebuilt = Delaunator::from_parts(c, t, h, hull).unwrap();
+    assert_eq!(rebuilt.coords(), &coords[..]);
+    assert_eq!(rebuilt.triangles, d.triangles);
+    assert_eq!(rebuilt.halfedges, d.halfedges);
+    assert_eq!(rebuilt.hull, d.hull);
+
+    // Flipped triangle, broken twin and shortened hull
+    let (c, mut t, h, hull) = parts();
+    t.swap(0, 1);
+    assert_eq!(
+        Delaunator::from_parts(c, t, h, hull).unwrap_err(),
+        Error::InvalidTriangulation
+    );
+    let (c, t, mut h, hull) = parts();
+    let e = h.iter().position(|&twin| twin >= 0).unwrap();
+    h[e] = -1;
+    assert_eq!(
+        Delaunator::from_parts(c, t, h, hull).unwrap_err(),
+        Error::InvalidTriangulation
+    );
+    let (c, t, h, mut hull) = parts();
+    hull.pop();
+    assert_eq!(
+        Delaunator::from_parts(c, t, h, hull).unwrap_err(),
+        Error::InvalidTriangulation
+    );
+
+    // Hull indices are checked even without triangles
+    assert!(Delaunator::from_parts(vec![0.0, 0.0, 1.0, 1.0], vec![], vec![], vec![0, 1]).is_ok());
+    assert_eq!(
+        Delaunator::from_parts(vec![0.0, 0.0, 1.0, 1.0], vec![], vec![], vec![0, 2]).unwrap_err(),
+        Error::InvalidTriangulation
+    );
+    assert_eq!(
+        Delaunator::from_parts(vec![0.0, f64::NAN], vec![], vec![], vec![0]).unwrap_err(),
+        Error::NonFiniteCoordinate
+    );
+}
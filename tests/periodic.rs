@@ -0,0 +1,163 @@
+//! Periodic triangulations checked against every copy of the points
+
+use delaunator_rs::periodic::PeriodicDelaunay;
+use delaunator_rs::{next_halfedge, Error};
+use proptest::prelude::*;
+use robust::{incircle, orient2d, Coord};
+
+fn points(width: f64, height: f64) -> impl Strategy<Value = Vec<f64>> {
+    prop::collection::vec((0.0..width, 0.0..height), 20..100)
+        .prop_map(|points| points.into_iter().flat_map(|(x, y)| [x, y]).collect())
+}
+
+fn coord((x, y): (f64, f64)) -> Coord<f64> {
+    Coord { x, y }
+}
+
+// Checks the twins, the counts and the empty circle of every triangle
+// against every point in each of the 9 tiles around the domain
+fn check(d: &PeriodicDelaunay, coords: &[f64], width: f64, height: f64) {
+    let n = coords.len() / 2;
+
+    // A triangulation of the torus has V - E + F = 0, so F = 2V
+    assert_eq!(d.triangles.len(), 3 * 2 * n);
+    assert_eq!(d.offsets.len(), 2 * d.triangles.len());
+
+    for e in 0..d.halfedges.len() {
+        let twin = d.halfedges[e] as usize;
+        assert_eq!(d.halfedges[twin], e as i32);
+        assert_eq!(d.triangles[e], d.triangles[next_halfedge(twin)]);
+
+        // Both sides see the edge as the same vector
+        let (a, b) = (d.corner(e), d.corner(next_halfedge(e)));
+        let (c, f) = (d.corner(next_halfedge(twin)), d.corner(twin));
+        assert!(((b.0 - a.0) - (f.0 - c.0)).abs() < 1e-9);
+        assert!(((b.1 - a.1) - (f.1 - c.1)).abs() < 1e-9);
+    }
+
+    let mut area = 0.0;
+    for t in (0..d.triangles.len()).step_by(3) {
+        let [a, b, c] = [t, t + 1, t + 2].map(|e| coord(d.corner(e)));
+        let orientation = orient2d(a, b, c);
+        assert!(orientation != 0.0, "triangle {} is flat", t / 3);
+        area += orientation.abs() / 2.0;
+
+        for i in 0..n {
+            for dx in -1..=1 {
+                for dy in -1..=1 {
+                    let p = Coord {
+                        x: coords[2 * i] + dx as f64 * width,
+                        y: coords[2 * i + 1] + dy as f64 * height,
+                    };
+                    assert!(
+                        incircle(a, b, c, p) * orientation <= 0.0,
+                        "copy ({dx}, {dy}) of point {i} is inside triangle {}",
+                        t / 3
+                    );
+                }
+            }
+        }
+    }
+    assert!((area - width * height).abs() < 1e-9 * width * height);
+}
+
+proptest! {
+    #[test]
+    fn random_points(coords in points(3.0, 2.0)) {
+        match PeriodicDelaunay::new(coords.clone(), 3.0, 2.0) {
+            Ok(d) => check(&d, &coords, 3.0, 2.0),
+            Err(error) => prop_assert_eq!(error, Error::TooSparse),
+        }
+    }
+}
+
+#[test]
+fn dense_grid() {
+    // Jittered so that no empty circle is as wide as the domain
+    let coords: Vec<f64> = (0..10)
+        .flat_map(|i| {
+            (0..10).flat_map(move |j| {
+                let jitter = ((i * 7 + j * 13) % 10) as f64 * 0.01;
+                [i as f64 + 0.3 + jitter, j as f64 + 0.5 - jitter]
+            })
+        })
+        .collect();
+    let d = PeriodicDelaunay::new(coords.clone(), 10.0, 10.0).unwrap();
+    check(&d, &coords, 10.0, 10.0);
+}
+
+#[test]
+fn too_sparse() {
+    // The copies of two points on the diagonal form a lattice, whose empty
+    // circles are as wide as the domain
+    assert_eq!(
+        PeriodicDelaunay::new(vec![0.1, 0.1, 0.5, 0.5], 1.0, 1.0).unwrap_err(),
+        Error::TooSparse
+    );
+}
+
+#[test]
+fn few_points() {
+    // Down to a single point, whose torus is two triangles
+    for coords in [
+        vec![0.5, 0.5],
+        vec![0.2, 0.3, 0.7, 0.6],
+        vec![0.1, 0.2, 0.4, 0.3, 0.8, 0.9],
+    ] {
+        let d = PeriodicDelaunay::new(coords.clone(), 1.0, 1.0).unwrap();
+        check(&d, &coords, 1.0, 1.0);
+    }
+}
+
+#[test]
+fn invalid_domain() {
+    for (width, height) in [
+        (0.0, 1.0),
+        (1.0, -1.0),
+        (f64::INFINITY, 1.0),
+        (1.0, f64::NAN),
+    ] {
+        assert_eq!(
+            PeriodicDelaunay::new(vec![0.5, 0.5], width, height).unwrap_err(),
+            Error::InvalidDomain
+        );
+    }
+}
+
+#[test]
+fn wrap_at_the_boundary() {
+    let base: Vec<f64> = (0..40)
+        .flat_map(|i| {
+            let i = i as f64;
+            [(i * 0.618_033_988_75) % 1.0, (i * 0.414_213_562_37) % 1.0]
+        })
+        .collect();
+    let reference = PeriodicDelaunay::new(base.clone(), 1.0, 1.0).unwrap();
+    check(&reference, &base, 1.0, 1.0);
+
+    // Point 0 is at the origin. Its copies on the far sides, one period
+    // away, and a tiny negative coordinate that rounds up to the width all
+    // wrap back to it.
+    assert_eq!(&base[..2], &[0.0, 0.0]);
+    for (x, y) in [
+        (1.0, 0.0),
+        (0.0, 1.0),
+        (1.0, 1.0),
+        (-1.0, 3.0),
+        (-1e-20, -0.0),
+    ] {
+        let mut coords = base.clone();
+        coords[0] = x;
+        coords[1] = y;
+        let d = PeriodicDelaunay::new(coords, 1.0, 1.0).unwrap();
+        assert_eq!(d.triangles, reference.triangles, "({x}, {y})");
+        assert_eq!(d.offsets, reference.offsets, "({x}, {y})");
+    }
+
+    // Points out of the domain wrap into it
+    let mut coords = base.clone();
+    coords[2] -= 5.0;
+    coords[3] += 2.0;
+    let d = PeriodicDelaunay::new(coords, 1.0, 1.0).unwrap();
+    assert_eq!(d.triangles, reference.triangles);
+}
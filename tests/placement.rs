@@ -0,0 +1,247 @@
+//! Largest empty circles and poles of inaccessibility checked against brute
+//! force
+
+use std::f64::consts::TAU;
+
+use delaunator_rs::calipers::Circle;
+use delaunator_rs::placement::pole_of_inaccessibility;
+use delaunator_rs::Delaunator;
+use proptest::prelude::*;
+
+fn random() -> impl Strategy<Value = Vec<f64>> {
+    prop::collection::vec((0.0..100.0, 0.0..100.0), 3..25)
+        .prop_map(|points| points.into_iter().flat_map(|(x, y)| [x, y]).collect())
+}
+
+// Star-shaped polygons around (50, 50), so always simple
+fn star() -> impl Strategy<Value = Vec<f64>> {
+    prop::collection::vec((0.0..1.0, 5.0..50.0), 3..20).prop_map(|mut spokes: Vec<(f64, f64)>| {
+        spokes.sort_by(|a, b| a.0.total_cmp(&b.0));
+        spokes
+            .into_iter()
+            .flat_map(|(angle, r)| {
+                let angle = angle * TAU;
+                [50.0 + r * angle.cos(), 50.0 + r * angle.sin()]
+            })
+            .collect()
+    })
+}
+
+fn distance(a: (f64, f64), b: (f64, f64)) -> f64 {
+    (b.0 - a.0).hypot(b.1 - a.1)
+}
+
+fn edges(rings: &[Vec<f64>]) -> impl Iterator<Item = ((f64, f64), (f64, f64))> + '_ {
+    rings.iter().flat_map(|ring| {
+        let n = ring.len() / 2;
+        (0..n).map(move |k| {
+            let l = (k + 1) % n;
+            (
+                (ring[2 * k], ring[2 * k + 1]),
+                (ring[2 * l], ring[2 * l + 1]),
+            )
+        })
+    })
+}
+
+// Even-odd test over all rings
+fn contains(rings: &[Vec<f64>], (x, y): (f64, f64)) -> bool {
+    let mut inside = false;
+    for (a, b) in edges(rings) {
+        if (a.1 > y) != (b.1 > y) && x < (b.0 - a.0) * (y - a.1) / (b.1 - a.1) + a.0 {
+            inside = !inside;
+        }
+    }
+    inside
+}
+
+fn boundary_distance(rings: &[Vec<f64>], p: (f64, f64)) -> f64 {
+    edges(rings)
+        .map(|(a, b)| {
+            let (dx, dy) = (b.0 - a.0, b.1 - a.1);
+            let t = (((p.0 - a.0) * dx + (p.1 - a.1) * dy) / (dx * dx + dy * dy)).clamp(0.0, 1.0);
+            distance(p, (a.0 + t * dx, a.1 + t * dy))
+        })
+        .fold(f64::INFINITY, f64::min)
+}
+
+fn nearest(coords: &[f64], p: (f64, f64)) -> f64 {
+    coords
+        .chunks_exact(2)
+        .map(|q| distance(p, (q[0], q[1])))
+        .fold(f64::INFINITY, f64::min)
+}
+
+// Largest empty circle among every circumcenter of three points inside the
+// region, every region vertex and every crossing of a bisector of two points
+// with the region boundary, all found without the triangulation
+fn brute_force_circle(coords: &[f64], rings: &[Vec<f64>]) -> f64 {
+    let points: Vec<(f64, f64)> = coords.chunks_exact(2).map(|p| (p[0], p[1])).collect();
+    let mut best: f64 = 0.0;
+
+    for (i, &a) in points.iter().enumerate() {
+        for (j, &b) in points.iter().enumerate().skip(i + 1) {
+            for &c in &points[j + 1..] {
+                let (bx, by) = (b.0 - a.0, b.1 - a.1);
+                let (cx, cy) = (c.0 - a.0, c.1 - a.1);
+                let d = 2.0 * (bx * cy - by * cx);
+                if d == 0.0 {
+                    continue;
+                }
+                let (bl, cl) = (bx * bx + by * by, cx * cx + cy * cy);
+                let center = (a.0 + (cy * bl - by * cl) / d, a.1 + (bx * cl - cx * bl) / d);
+                if contains(rings, center) {
+                    best = best.max(nearest(coords, center));
+                }
+            }
+
+            // Points of the edges as far from a as from b
+            for (p, q) in edges(rings) {
+                let (dx, dy) = (q.0 - p.0, q.1 - p.1);
+                let towards = dx * (b.0 - a.0) + dy * (b.1 - a.1);
+                if towards == 0.0 {
+                    continue;
+                }
+                let offset = b.0 * b.0 + b.1 * b.1 - a.0 * a.0 - a.1 * a.1;
+                let t = (offset - 2.0 * (p.0 * (b.0 - a.0) + p.1 * (b.1 - a.1))) / (2.0 * towards);
+                if (0.0..=1.0).contains(&t) {
+                    best = best.max(nearest(coords, (p.0 + t * dx, p.1 + t * dy)));
+                }
+            }
+        }
+    }
+
+    for (p, _) in edges(rings) {
+        best = best.max(nearest(coords, p));
+    }
+    best
+}
+
+fn check_circle(coords: &[f64], rings: &[Vec<f64>], circle: Circle) {
+    let center = (circle.x, circle.y);
+    let r = circle.r;
+    let tolerance = 1e-9 * 100.0;
+    assert!(
+        nearest(coords, center) >= r - tolerance,
+        "{circle:?} contains a point"
+    );
+    assert!(
+        contains(rings, center) || boundary_distance(rings, center) <= tolerance,
+        "{circle:?} is centered outside of the region"
+    );
+    let expected = brute_force_circle(coords, rings);
+    assert!(
+        (r - expected).abs() <= tolerance,
+        "radius {r} instead of {expected}"
+    );
+}
+
+fn hull_ring(d: &Delaunator, coords: &[f64]) -> Vec<f64> {
+    d.hull
+        .iter()
+        .flat_map(|&i| [coords[2 * i as usize], coords[2 * i as usize + 1]])
+        .collect()
+}
+
+// Checks that the pole is inside the polygon at the distance it reports from
+// the boundary, and no farther from it than a fine grid finds
+fn check_pole(rings: &[Vec<f64>], precision: f64) -> Circle {
+    let pole = pole_of_inaccessibility(rings, precision).unwrap();
+    let center = (pole.x, pole.y);
+    assert!(contains(rings, center), "{pole:?} is outside");
+    assert!((boundary_distance(rings, center) - pole.r).abs() <= 1e-9);
+
+    let step = 0.5;
+    let mut best: f64 = 0.0;
+    for i in 0..=200 {
+        for j in 0..=200 {
+            let p = (i as f64 * step, j as f64 * step);
+            if contains(rings, p) {
+                best = best.max(boundary_distance(rings, p));
+            }
+        }
+    }
+    assert!(
+        pole.r >= best - precision - 1e-9,
+        "the pole at distance {} misses a point at {best}",
+        pole.r
+    );
+    assert!(
+        pole.r <= best + step,
+        "the pole at distance {} is farther than any point of the grid, {best}",
+        pole.r
+    );
+    pole
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(64))]
+
+    #[test]
+    fn largest_empty_circle_in_the_hull(coords in random()) {
+        let d = Delaunator::new(coords.clone()).unwrap();
+        if let Some(circle) = d.largest_empty_circle(None) {
+            check_circle(&coords, &[hull_ring(&d, &coords)], circle);
+        }
+    }
+
+    #[test]
+    fn largest_empty_circle_in_a_polygon(coords in random(), ring in star()) {
+        let d = Delaunator::new(coords.clone()).unwrap();
+        let hole = vec![45.0, 45.0, 45.0, 55.0, 55.0, 55.0, 55.0, 45.0];
+        for rings in [vec![ring.clone()], vec![ring.clone(), hole]] {
+            if let Some(circle) = d.largest_empty_circle(Some(&rings)) {
+                check_circle(&coords, &rings, circle);
+            }
+        }
+    }
+
+    #[test]
+    fn pole_of_star(ring in star(), precision in prop_oneof![Just(0.0), 0.01..1.0]) {
+        check_pole(&[ring], precision);
+    }
+}
+
+#[test]
+fn pole_of_square_with_hole() {
+    let outer = vec![0.0, 0.0, 0.0, 100.0, 100.0, 100.0, 100.0, 0.0];
+    let hole = vec![20.0, 20.0, 20.0, 60.0, 60.0, 60.0, 60.0, 20.0];
+    let rings = [outer, hole];
+    let pole = check_pole(&rings[..1], 0.01);
+    assert!((pole.r - 50.0).abs() <= 0.01);
+
+    // On the diagonal beyond the hole, as far from its corner as from the
+    // two outer sides
+    let pole = check_pole(&rings, 0.01);
+    assert!((pole.r - 40.0 * (2.0 - 2f64.sqrt())).abs() <= 0.01 + 1e-9);
+}
+
+#[test]
+fn pole_precision_out_of_range() {
+    // Each of these would refine cells forever
+    let rings = [vec![0.0, 0.0, 10.0, 0.0, 10.0, 10.0, 0.0, 10.0]];
+    for precision in [0.0, -1.0, f64::NAN, f64::MIN_POSITIVE] {
+        let pole = pole_of_inaccessibility(&rings, precision).unwrap();
+        assert!((pole.r - 5.0).abs() <= 1e-7, "{precision}: {pole:?}");
+    }
+}
+
+// A precision wider than the polygon stops at the best seed, which is the
+// centroid of this triangle, also far from the origin
+#[test]
+fn centroid_seed_far_from_origin() {
+    for (x, y) in [(0.0, 0.0), (1e9, -3e9)] {
+        let rings = [vec![x, y, x + 6.0, y, x, y + 6.0]];
+        let pole = pole_of_inaccessibility(&rings, 100.0).unwrap();
+        assert_eq!((pole.x, pole.y), (x + 2.0, y + 2.0));
+    }
+}
+
+#[test]
+fn degenerate_input() {
+    assert!(pole_of_inaccessibility(&[], 1.0).is_none());
+    assert!(pole_of_inaccessibility(&[vec![0.0, 0.0, 1.0, 1.0]], 1.0).is_none());
+
+    let collinear = Delaunator::new(vec![0.0, 0.0, 1.0, 1.0, 2.0, 2.0]).unwrap();
+    assert!(collinear.largest_empty_circle(None).is_none());
+}
@@ -0,0 +1,340 @@
+//! Property-based tests over families of point sets, including the
+//! degenerate ones that break naive triangulators
+
+mod common;
+
+use std::collections::HashMap;
+
+use delaunator_rs::stream;
+use delaunator_rs::{Delaunator, Index, Options, Order, Sort, Triangulation};
+use proptest::prelude::*;
+use robust::{orient2d, Coord};
+
+fn flatten(points: Vec<(f64, f64)>) -> Vec<f64> {
+    points.into_iter().flat_map(|(x, y)| [x, y]).collect()
+}
+
+fn stream_triangles(coords: &[f64]) -> Vec<usize> {
+    let points = || coords.chunks_exact(2).map(|p| (p[0], p[1]));
+    stream::triangulate(points, Vec::new()).unwrap()
+}
+
+// Streamed triangles with their halfedges and hull rebuilt, checked by
+// `from_parts` as a whole triangulation
+fn streamed_triangulation(coords: &[f64]) -> Delaunator {
+    let triangles: Vec<u32> = stream_triangles(coords)
+        .into_iter()
+        .map(|i| i as u32)
+        .collect();
+    let next = |e: usize| if e % 3 == 2 { e - 2 } else { e + 1 };
+    let edges: HashMap<(u32, u32), usize> = (0..triangles.len())
+        .map(|e| ((triangles[e], triangles[next(e)]), e))
+        .collect();
+    let halfedges: Vec<i32> = (0..triangles.len())
+        .map(|e| {
+            edges
+                .get(&(triangles[next(e)], triangles[e]))
+                .map_or(-1, |&t| t as i32)
+        })
+        .collect();
+
+    // Boundary edges chain into the hull from any of their points
+    let boundary: HashMap<u32, u32> = (0..triangles.len())
+        .filter(|&e| halfedges[e] == -1)
+        .map(|e| (triangles[e], triangles[next(e)]))
+        .collect();
+    let mut hull = Vec::new();
+    if let Some(&start) = boundary.keys().min() {
+        let mut p = start;
+        loop {
+            hull.push(p);
+            p = boundary[&p];
+            if p == start || hull.len() > boundary.len() {
+                break;
+            }
+        }
+    }
+
+    Delaunator::from_parts(coords.to_vec(), triangles, halfedges, hull)
+        .expect("streamed triangles form a valid triangulation")
+}
+
+// Triangles rotated to start at their lowest corner, in increasing order
+fn canonical(triangles: impl IntoIterator<Item = usize>) -> Vec<[usize; 3]> {
+    let triangles: Vec<usize> = triangles.into_iter().collect();
+    let mut canonical: Vec<[usize; 3]> = triangles
+        .chunks_exact(3)
+        .map(|t| {
+            let k = (0..3).min_by_key(|&k| t[k]).unwrap();
+            [t[k], t[(k + 1) % 3], t[(k + 2) % 3]]
+        })
+        .collect();
+    canonical.sort_unstable();
+    canonical
+}
+
+fn random() -> impl Strategy<Value = Vec<f64>> {
+    prop::collection::vec((-1e3..1e3, -1e3..1e3), 3..300).prop_map(flatten)
+}
+
+// Regular grids are full of cocircular quadruples
+fn grid() -> impl Strategy<Value = Vec<f64>> {
+    (2..20usize, 2..20usize, 0.1..10.0f64).prop_map(|(w, h, spacing)| {
+        let mut coords = Vec::with_capacity(2 * w * h);
+        for i in 0..w {
+            for j in 0..h {
+                coords.extend([i as f64 * spacing, j as f64 * spacing]);
+            }
+        }
+        coords
+    })
+}
+
+// Integer steps along an integer direction, so exactly collinear
+fn collinear() -> impl Strategy<Value = Vec<f64>> {
+    (
+        (-100..100i32, -100..100i32),
+        (-10..10i32, -10..10i32).prop_filter("nonzero direction", |&d| d != (0, 0)),
+        prop::collection::vec(-50..50i32, 3..50),
+    )
+        .prop_map(|((ox, oy), (dx, dy), steps)| {
+            steps
+                .into_iter()
+                .flat_map(|t| [(ox + t * dx) as f64, (oy + t * dy) as f64])
+                .collect()
+        })
+}
+
+// Lattice points of the circle of radius 65, which has 36 of them, so
+// exactly cocircular, optionally with the center
+fn cocircular() -> impl Strategy<Value = Vec<f64>> {
+    let lattice: Vec<(f64, f64)> = (-65..=65i32)
+        .flat_map(|x| (-65..=65i32).map(move |y| (x, y)))
+        .filter(|&(x, y)| x * x + y * y == 65 * 65)
+        .map(|(x, y)| (x as f64, y as f64))
+        .collect();
+    (
+        prop::sample::subsequence(lattice.clone(), 3..=lattice.len()),
+        any::<bool>(),
+    )
+        .prop_filter("not collinear", |(points, _)| {
+            points.len() > 3 || {
+                let [a, b, c] = [points[0], points[1], points[2]];
+                (b.0 - a.0) * (c.1 - a.1) != (b.1 - a.1) * (c.0 - a.0)
+            }
+        })
+        .prop_map(|(points, center)| {
+            let mut coords = flatten(points);
+            if center {
+                coords.extend([0.0, 0.0]);
+            }
+            coords
+        })
+}
+
+// Few distinct points, each repeated many times
+fn duplicates() -> impl Strategy<Value = Vec<f64>> {
+    prop::collection::vec((-1e3..1e3, -1e3..1e3), 3..10).prop_flat_map(|points| {
+        prop::collection::vec(prop::sample::select(points), 10..200).prop_map(flatten)
+    })
+}
+
+// Small spread far from the origin, where rounding hurts the most
+fn huge_offset() -> impl Strategy<Value = Vec<f64>> {
+    (
+        prop::collection::vec((0.0..1.0, 0.0..1.0), 3..200),
+        prop::sample::select(vec![1e6, 1e9, -1e9, 1e12]),
+    )
+        .prop_map(|(points, offset)| {
+            points
+                .into_iter()
+                .flat_map(|(x, y)| [x + offset, y + offset])
+                .collect()
+        })
+}
+
+proptest! {
+    #[test]
+    fn random_points(coords in random()) {
+        common::triangulate(&coords);
+    }
+
+    #[test]
+    fn grid_points(coords in grid()) {
+        common::triangulate(&coords);
+    }
+
+    #[test]
+    fn collinear_points(coords in collinear()) {
+        let d = common::triangulate(&coords);
+        prop_assert!(d.triangles.is_empty());
+    }
+
+    #[test]
+    fn cocircular_points(coords in cocircular()) {
+        common::triangulate(&coords);
+    }
+
+    #[test]
+    fn duplicate_points(coords in duplicates()) {
+        common::triangulate(&coords);
+    }
+
+    #[test]
+    fn huge_offset_points(coords in huge_offset()) {
+        common::triangulate(&coords);
+    }
+
+    // Both sorts order ties by point id, so they insert the points in the
+    // same order
+    #[test]
+    fn sort_algorithms(coords in prop_oneof![random(), grid(), duplicates()]) {
+        let with_sort = |sort| {
+            let d = Delaunator::with_options(coords.clone(), Options { sort, ..Options::default() }).unwrap();
+            common::check(&d, &coords);
+            d
+        };
+        let radix = with_sort(Sort::Radix);
+        let comparison = with_sort(Sort::Comparison);
+        with_sort(Sort::Quicksort);
+        prop_assert_eq!(radix.triangles, comparison.triangles);
+        prop_assert_eq!(radix.hull, comparison.hull);
+    }
+
+    #[test]
+    fn insertion_orders(
+        coords in prop_oneof![random(), grid(), collinear(), cocircular(), duplicates()],
+    ) {
+        let sweep = common::triangulate(&coords);
+        for order in [Order::Hilbert, Order::Brio] {
+            let options = Options { order, ..Options::default() };
+            let d = Delaunator::with_options(coords.clone(), options).unwrap();
+            common::check(&d, &coords);
+            prop_assert_eq!(d.triangles.len(), sweep.triangles.len());
+            prop_assert!(d.hidden.is_empty());
+        }
+    }
+
+    #[test]
+    fn renumbered_points(coords in prop_oneof![random(), grid(), duplicates()]) {
+        let mut d = common::triangulate(&coords);
+        let triangles = d.triangles.clone();
+        let previous = d.renumber();
+
+        let renumbered: Vec<f64> = previous
+            .iter()
+            .flat_map(|&i| [coords[2 * i as usize], coords[2 * i as usize + 1]])
+            .collect();
+        common::check(&d, &renumbered);
+
+        // Same triangles, as sets of original points
+        let corners = |triangles: &[u32], map: &dyn Fn(u32) -> u32| {
+            let mut corners: Vec<[u32; 3]> = triangles
+                .chunks_exact(3)
+                .map(|t| {
+                    let mut t = [map(t[0]), map(t[1]), map(t[2])];
+                    t.sort_unstable();
+                    t
+                })
+                .collect();
+            corners.sort_unstable();
+            corners
+        };
+        prop_assert_eq!(
+            corners(&d.triangles, &|i| previous[i as usize]),
+            corners(&triangles, &|i| i)
+        );
+    }
+
+    // Every index type runs the same sweep as Delaunator
+    #[test]
+    fn index_types(
+        coords in prop_oneof![random(), grid(), collinear(), cocircular(), duplicates()],
+    ) {
+        fn widen<I: Index>(indices: &[I]) -> Vec<i64> {
+            indices
+                .iter()
+                .map(|&i| if i == I::NONE { -1 } else { i.to_usize() as i64 })
+                .collect()
+        }
+
+        let d = common::triangulate(&coords);
+        let triangles: Vec<i64> = d.triangles.iter().map(|&i| i as i64).collect();
+        let halfedges: Vec<i64> = d.halfedges.iter().map(|&h| h as i64).collect();
+        let hull: Vec<i64> = d.hull.iter().map(|&i| i as i64).collect();
+
+        let small = Triangulation::<u16>::new(coords.clone()).unwrap();
+        prop_assert_eq!(widen(&small.triangles), triangles.clone());
+        prop_assert_eq!(widen(&small.halfedges), halfedges.clone());
+        prop_assert_eq!(widen(&small.hull), hull.clone());
+
+        let large = Triangulation::<u64>::new(coords).unwrap();
+        prop_assert_eq!(widen(&large.triangles), triangles);
+        prop_assert_eq!(widen(&large.halfedges), halfedges);
+        prop_assert_eq!(widen(&large.hull), hull);
+    }
+
+    // Every triangulation passes the checks of from_parts
+    #[test]
+    fn rebuilt_from_parts(
+        coords in prop_oneof![random(), grid(), collinear(), cocircular(), duplicates()],
+    ) {
+        let d = common::triangulate(&coords);
+        let rebuilt = Delaunator::from_parts(
+            coords.clone(),
+            d.triangles.clone(),
+            d.halfedges.clone(),
+            d.hull.clone(),
+        );
+        prop_assert!(rebuilt.is_ok());
+    }
+
+    // The binary format reads back the same arrays
+    #[cfg(feature = "std")]
+    #[test]
+    fn binary_round_trip(
+        coords in prop_oneof![random(), grid(), collinear(), duplicates(), huge_offset()],
+    ) {
+        let d = common::triangulate(&coords);
+        let mut bytes = Vec::new();
+        d.write_to(&mut bytes).unwrap();
+        let read = Delaunator::read_from(&bytes[..]).unwrap();
+        prop_assert_eq!(read.coords(), &coords[..]);
+        prop_assert_eq!(read.triangles, d.triangles);
+        prop_assert_eq!(read.halfedges, d.halfedges);
+        prop_assert_eq!(read.hull, d.hull);
+    }
+
+    // Streaming gives the same triangles, which are unique for points in
+    // general position
+    #[test]
+    fn streamed_points(coords in prop_oneof![random(), huge_offset()]) {
+        let d = common::triangulate(&coords);
+        let streamed = stream_triangles(&coords);
+        prop_assert_eq!(
+            canonical(streamed),
+            canonical(d.triangles.iter().map(|&i| i as usize))
+        );
+    }
+
+    // Degenerate points may have several triangulations, all with as many
+    // triangles, in the same orientation, and each of them Delaunay
+    #[test]
+    fn streamed_degenerate_points(
+        coords in prop_oneof![grid(), collinear(), cocircular(), duplicates()],
+    ) {
+        let d = common::triangulate(&coords);
+        let streamed = streamed_triangulation(&coords);
+        prop_assert_eq!(streamed.triangles.len(), d.triangles.len());
+        for t in streamed.triangles.chunks_exact(3) {
+            let [a, b, c] = [t[0], t[1], t[2]].map(|i| Coord {
+                x: coords[2 * i as usize],
+                y: coords[2 * i as usize + 1],
+            });
+            prop_assert!(orient2d(a, b, c) < 0.0);
+        }
+        if !streamed.triangles.is_empty() {
+            common::check(&streamed, &coords);
+        }
+    }
+}
@@ -0,0 +1,122 @@
+//! Triangle quality metrics on known shapes and their histograms
+
+mod common;
+
+use delaunator_rs::quality::Histogram;
+use delaunator_rs::Delaunator;
+use proptest::prelude::*;
+
+fn random() -> impl Strategy<Value = Vec<f64>> {
+    prop::collection::vec((-1e3..1e3, -1e3..1e3), 3..100)
+        .prop_map(|points| points.into_iter().flat_map(|(x, y)| [x, y]).collect())
+}
+
+fn close(a: f64, b: f64) -> bool {
+    (a - b).abs() <= 1e-12 * (1.0 + b.abs())
+}
+
+#[test]
+fn equilateral() {
+    let h = 3f64.sqrt() / 2.0;
+    for scale in [1e-3, 1.0, 1e3] {
+        let coords: Vec<f64> = [0.0, 0.0, 1.0, 0.0, 0.5, h]
+            .iter()
+            .map(|v| v * scale)
+            .collect();
+        let d = common::triangulate(&coords);
+        let q = d.triangle_quality(0);
+        assert!(close(q.aspect_ratio, 1.0), "{q:?}");
+        assert!(
+            close(q.min_angle, 60.0) && close(q.max_angle, 60.0),
+            "{q:?}"
+        );
+        assert!(close(q.radius_edge_ratio, 1.0 / 3f64.sqrt()), "{q:?}");
+        assert!(close(q.area, h / 2.0 * scale * scale), "{q:?}");
+    }
+}
+
+#[test]
+fn right_isosceles() {
+    let d = common::triangulate(&[0.0, 0.0, 2.0, 0.0, 0.0, 2.0]);
+    let q = d.triangle_quality(0);
+    assert!(
+        close(q.min_angle, 45.0) && close(q.max_angle, 90.0),
+        "{q:?}"
+    );
+    assert!(close(q.aspect_ratio, (1.0 + 2f64.sqrt()) / 2.0), "{q:?}");
+    assert!(close(q.radius_edge_ratio, 2f64.sqrt() / 2.0), "{q:?}");
+    assert_eq!(q.area, 2.0);
+}
+
+// The triangulation never makes flat triangles, so one is set by hand
+#[test]
+fn degenerate() {
+    let mut d = Delaunator::new(vec![0.0, 0.0, 1.0, 0.0, 3.0, 0.0]).unwrap();
+    d.triangles = vec![0, 1, 2];
+    let q = d.triangle_quality(0);
+    assert_eq!(q.min_angle, 0.0);
+    assert_eq!(q.max_angle, 180.0);
+    assert_eq!(q.area, 0.0);
+    assert_eq!(q.aspect_ratio, f64::INFINITY);
+    assert_eq!(q.radius_edge_ratio, f64::INFINITY);
+
+    // Infinite ratios are left out of the range but still counted
+    let report = d.quality_report(4);
+    assert_eq!(report.min_angle.histogram.counts, [1, 0, 0, 0]);
+    assert_eq!(report.max_angle.histogram.counts, [0, 0, 0, 1]);
+    assert_eq!(
+        report.aspect_ratio.histogram.counts.iter().sum::<usize>(),
+        1
+    );
+}
+
+#[test]
+fn histogram_bins() {
+    let h = Histogram::new(
+        [0.0, 0.5, 2.5, 9.99, 10.0, -3.0, 42.0, f64::NAN],
+        0.0,
+        10.0,
+        4,
+    );
+    assert_eq!(h.counts, [3, 1, 0, 3]);
+    assert_eq!(h.bin_width(), 2.5);
+
+    // An empty range puts everything into the first bin, and no bins is one
+    let h = Histogram::new([1.0, 1.0, 1.0], 1.0, 1.0, 0);
+    assert_eq!(h.counts, [3]);
+}
+
+proptest! {
+    #[test]
+    fn random_points(coords in random(), bins in 1..20usize) {
+        let d = common::triangulate(&coords);
+        let quality = d.quality();
+        let n = d.triangles.len() / 3;
+        prop_assert_eq!(quality.len(), n);
+        prop_assert_eq!(d.triangle_quality_values().len(), 5 * n);
+
+        for q in &quality {
+            prop_assert!(q.min_angle > 0.0 && q.min_angle <= 60.0 + 1e-9);
+            prop_assert!(q.max_angle >= 60.0 - 1e-9 && q.max_angle < 180.0);
+            prop_assert!(q.aspect_ratio >= 1.0 - 1e-9);
+            prop_assert!(q.radius_edge_ratio >= 1.0 / 3f64.sqrt() - 1e-9);
+            prop_assert!(q.area > 0.0);
+        }
+
+        let report = d.quality_report(bins);
+        for summary in [
+            &report.min_angle,
+            &report.max_angle,
+            &report.aspect_ratio,
+            &report.area,
+            &report.radius_edge_ratio,
+        ] {
+            prop_assert_eq!(summary.histogram.counts.len(), bins);
+            prop_assert_eq!(summary.histogram.counts.iter().sum::<usize>(), n);
+            prop_assert!(summary.min <= summary.mean * (1.0 + 1e-9));
+            prop_assert!(summary.mean <= summary.max * (1.0 + 1e-9));
+        }
+        let total: f64 = quality.iter().map(|q| q.area).sum();
+        prop_assert!((report.area.mean * n as f64 - total).abs() <= 1e-9 * total);
+    }
+}
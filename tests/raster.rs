@@ -0,0 +1,107 @@
+//! Rasterization of a known mesh and its ASCII Grid output
+
+#[cfg(feature = "std")]
+use delaunator_rs::raster::Grid;
+use delaunator_rs::Delaunator;
+
+// Linear, so interpolation over any triangle gives it back exactly
+fn plane(x: f64, y: f64) -> f64 {
+    2.0 * x + 3.0 * y + 1.0
+}
+
+#[test]
+fn fills_the_hull() {
+    // Right triangle with legs 4 and 3 and a point inside, so that scanlines
+    // cross the edges shared by its three triangles. No cell center is on
+    // the hypotenuse 3x + 4y = 12.
+    let coords = vec![0.0, 0.0, 4.0, 0.0, 0.0, 3.0, 1.0, 1.0];
+    let values: Vec<f64> = coords.chunks(2).map(|p| plane(p[0], p[1])).collect();
+    let d = Delaunator::new(coords).unwrap();
+    assert_eq!(d.triangles.len(), 9);
+
+    let grid = d.rasterize(&values, [0.0, 0.0, 4.0, 4.0], 8, 8);
+    assert_eq!(grid.data.len(), 64);
+    assert_eq!(grid.cell_size(), (0.5, 0.5));
+
+    let mut filled = 0;
+    for row in 0..8 {
+        for col in 0..8 {
+            let x = 0.25 + col as f64 * 0.5;
+            let y = 4.0 - 0.25 - row as f64 * 0.5;
+            let value = grid.get(col, row);
+            if 3.0 * x + 4.0 * y < 12.0 {
+                assert!(
+                    (value - plane(x, y)).abs() < 1e-12,
+                    "cell ({col}, {row}) is {value}"
+                );
+                filled += 1;
+            } else {
+                assert!(
+                    value.is_nan(),
+                    "cell ({col}, {row}) outside the hull is {value}"
+                );
+            }
+        }
+    }
+    // 24 of the 64 cell centers are inside the triangle
+    assert_eq!(filled, 24);
+}
+
+#[test]
+fn empty_grid() {
+    let d = Delaunator::new(vec![0.0, 0.0, 1.0, 0.0, 0.0, 1.0]).unwrap();
+    let grid = d.rasterize(&[1.0, 2.0, 3.0], [0.0, 0.0, 1.0, 1.0], 0, 4);
+    assert!(grid.data.is_empty());
+
+    // Bounds away from the hull leave every cell NaN
+    let grid = d.rasterize(&[1.0, 2.0, 3.0], [5.0, 5.0, 6.0, 6.0], 3, 3);
+    assert!(grid.data.iter().all(|v| v.is_nan()));
+}
+
+#[test]
+#[should_panic(expected = "expected one value per point")]
+fn value_count() {
+    let d = Delaunator::new(vec![0.0, 0.0, 1.0, 0.0, 0.0, 1.0]).unwrap();
+    d.rasterize(&[1.0, 2.0], [0.0, 0.0, 1.0, 1.0], 2, 2);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn write_asc() {
+    let grid = Grid {
+        width: 3,
+        height: 2,
+        bounds: [10.0, 20.0, 13.0, 22.0],
+        data: vec![1.0, 2.0, f64::NAN, 4.0, 5.5, -6.0],
+    };
+    let mut out = Vec::new();
+    grid.write_asc(&mut out).unwrap();
+    assert_eq!(
+        String::from_utf8(out).unwrap(),
+        "ncols 3\n\
+         nrows 2\n\
+         xllcorner 10\n\
+         yllcorner 20\n\
+         cellsize 1\n\
+         NODATA_value -9999\n\
+         1 2 -9999\n\
+         4 5.5 -6\n"
+    );
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn write_asc_rectangular_cells() {
+    let grid = Grid {
+        width: 2,
+        height: 1,
+        bounds: [0.0, 0.0, 1.0, 2.0],
+        data: vec![0.25, f64::NAN],
+    };
+    let mut out = Vec::new();
+    grid.write_asc(&mut out).unwrap();
+    assert_eq!(
+        String::from_utf8(out).unwrap(),
+        "ncols 2\nnrows 1\nxllcorner 0\nyllcorner 0\ndx 0.5\ndy 2\nNODATA_value -9999\n0.25 -9999\n"
+    );
+}
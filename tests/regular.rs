@@ -0,0 +1,274 @@
+//! Regular triangulations and power diagrams checked against every point
+
+use std::collections::HashSet;
+
+use delaunator_rs::{Delaunator, Error};
+use proptest::prelude::*;
+use robust::{incircle, orient2d, orient3d, Coord, Coord3D};
+
+// Distinct integer points, small enough that lifting them with integer
+// weights is exact
+fn points() -> impl Strategy<Value = Vec<(i32, i32)>> {
+    prop::collection::vec((-100..100i32, -100..100i32), 3..100).prop_map(|points| {
+        let mut seen = HashSet::new();
+        points.into_iter().filter(|&p| seen.insert(p)).collect()
+    })
+}
+
+fn random_weights() -> impl Strategy<Value = (Vec<f64>, Vec<f64>)> {
+    points().prop_flat_map(|points| {
+        let n = points.len();
+        (Just(points), prop::collection::vec(-2000..2000i32, n..=n)).prop_map(
+            |(points, weights)| {
+                let coords = points.iter().flat_map(|&(x, y)| [x as f64, y as f64]);
+                (
+                    coords.collect(),
+                    weights.into_iter().map(f64::from).collect(),
+                )
+            },
+        )
+    })
+}
+
+// Weights lifting every point onto the plane z = ax + by + c, so that every
+// four points are on the same lifted plane
+fn coplanar_weights() -> impl Strategy<Value = (Vec<f64>, Vec<f64>)> {
+    (points(), -10..10i32, -10..10i32, -100..100i32).prop_map(|(points, a, b, c)| {
+        let coords = points.iter().flat_map(|&(x, y)| [x as f64, y as f64]);
+        let weights = points
+            .iter()
+            .map(|&(x, y)| (x * x + y * y - (a * x + b * y + c)) as f64);
+        (coords.collect(), weights.collect())
+    })
+}
+
+fn lift(coords: &[f64], weights: &[f64], i: u32) -> Coord3D<f64> {
+    let (x, y) = (coords[2 * i as usize], coords[2 * i as usize + 1]);
+    Coord3D {
+        x,
+        y,
+        z: x * x + y * y - weights[i as usize],
+    }
+}
+
+// Checks that the lifted triangles lie below every lifted point, hidden or
+// not, and that the points left out are exactly the hidden ones
+fn check(d: &Delaunator, coords: &[f64], weights: &[f64]) {
+    let report = d.validate();
+    assert!(report.is_valid(), "{report}");
+
+    let n = coords.len() / 2;
+    for t in d.triangles.chunks_exact(3) {
+        let [a, b, c] = [t[0], t[1], t[2]].map(|i| lift(coords, weights, i));
+        for p in 0..n as u32 {
+            assert!(
+                orient3d(a, b, c, lift(coords, weights, p)) >= 0.0,
+                "point {p} is below the lifted triangle {t:?}"
+            );
+        }
+    }
+
+    let used: HashSet<u32> = d.triangles.iter().chain(&d.hull).copied().collect();
+    for i in 0..n as u32 {
+        assert_ne!(
+            used.contains(&i),
+            d.hidden.contains(&i),
+            "point {i} is either used and hidden, or neither"
+        );
+    }
+    if !d.triangles.is_empty() {
+        let vertices = used.len();
+        assert_eq!(d.triangles.len() / 3, 2 * vertices - d.hull.len() - 2);
+    }
+}
+
+// Lowest power distance from (x, y) to any point
+fn power_distance(coords: &[f64], weights: &[f64], i: usize, (x, y): (f64, f64)) -> f64 {
+    let dx = x - coords[2 * i];
+    let dy = y - coords[2 * i + 1];
+    dx * dx + dy * dy - weights[i]
+}
+
+// Checks that the cells tile the rectangle, wind counterclockwise and are
+// made of the places closest to their point in power distance
+fn check_cells(d: &Delaunator, coords: &[f64], weights: &[f64], bounds: [f64; 4]) {
+    let n = coords.len() / 2;
+    let cells = d.power_cells(bounds);
+    assert_eq!(cells.len(), n);
+
+    let mut total = 0.0;
+    for (i, cell) in cells.iter().enumerate() {
+        assert_eq!(cell, &d.power_cell(i, bounds));
+        if d.hidden.contains(&(i as u32)) {
+            assert!(cell.is_empty(), "hidden point {i} has a cell");
+        }
+        if cell.is_empty() {
+            continue;
+        }
+
+        let m = cell.len() / 2;
+        let area: f64 = (0..m)
+            .map(|k| {
+                let (ax, ay) = (cell[2 * k], cell[2 * k + 1]);
+                let (bx, by) = (cell[2 * ((k + 1) % m)], cell[2 * ((k + 1) % m) + 1]);
+                (ax * by - bx * ay) / 2.0
+            })
+            .sum();
+        assert!(area >= -1e-6, "cell {i} winds clockwise");
+        total += area;
+
+        let center = (
+            cell.iter().step_by(2).sum::<f64>() / m as f64,
+            cell.iter().skip(1).step_by(2).sum::<f64>() / m as f64,
+        );
+        let own = power_distance(coords, weights, i, center);
+        for j in 0..n {
+            let other = power_distance(coords, weights, j, center);
+            assert!(
+                own <= other + 1e-6 * (1.0 + other.abs()),
+                "the center of cell {i} is closer to point {j}"
+            );
+        }
+    }
+
+    let [min_x, min_y, max_x, max_y] = bounds;
+    let expected = (max_x - min_x) * (max_y - min_y);
+    assert!(
+        (total - expected).abs() <= 1e-9 * expected,
+        "cells cover {total} instead of {expected}"
+    );
+}
+
+proptest! {
+    #[test]
+    fn regular_property((coords, weights) in prop_oneof![random_weights(), coplanar_weights()]) {
+        let d = Delaunator::with_weights(coords.clone(), weights.clone()).unwrap();
+        check(&d, &coords, &weights);
+    }
+
+    #[test]
+    fn power_cells((coords, weights) in random_weights()) {
+        let d = Delaunator::with_weights(coords.clone(), weights.clone()).unwrap();
+        check_cells(&d, &coords, &weights, [-150.0, -150.0, 150.0, 150.0]);
+        check_cells(&d, &coords, &weights, [-20.0, 0.0, 30.0, 10.0]);
+    }
+
+    // Equal weights give the Delaunay triangulation
+    #[test]
+    fn equal_weights(coords in points(), weight in -100.0..100.0f64) {
+        let coords: Vec<f64> = coords.iter().flat_map(|&(x, y)| [x as f64, y as f64]).collect();
+        let weights = vec![weight; coords.len() / 2];
+        let d = Delaunator::with_weights(coords.clone(), weights).unwrap();
+        let report = d.validate();
+        prop_assert!(report.is_valid(), "{}", report);
+        check_delaunay(&d, &coords);
+        prop_assert!(d.hidden.is_empty());
+        prop_assert_eq!(d.triangles.len(), Delaunator::new(coords).unwrap().triangles.len());
+    }
+}
+
+#[test]
+fn hidden_points() {
+    // The center of the square is hidden by a light enough weight, and
+    // hides no one with a heavy one
+    let coords = vec![0.0, 0.0, 10.0, 0.0, 10.0, 10.0, 0.0, 10.0, 5.0, 5.0];
+    let bounds = [-5.0, -5.0, 15.0, 15.0];
+
+    let weights = vec![0.0, 0.0, 0.0, 0.0, -60.0];
+    let light = Delaunator::with_weights(coords.clone(), weights.clone()).unwrap();
+    check(&light, &coords, &weights);
+    assert_eq!(light.hidden, [4]);
+    assert_eq!(light.triangles.len(), 6);
+    assert!(light.power_cell(4, bounds).is_empty());
+    check_cells(&light, &coords, &weights, bounds);
+
+    // Lifted onto the plane of the corners, its cell shrinks to a point
+    let weights = vec![0.0, 0.0, 0.0, 0.0, -50.0];
+    let level = Delaunator::with_weights(coords.clone(), weights.clone()).unwrap();
+    check(&level, &coords, &weights);
+
+    let weights = vec![0.0, 0.0, 0.0, 0.0, 30.0];
+    let heavy = Delaunator::with_weights(coords.clone(), weights.clone()).unwrap();
+    check(&heavy, &coords, &weights);
+    assert!(heavy.hidden.is_empty());
+    assert_eq!(heavy.triangles.len(), 12);
+    check_cells(&heavy, &coords, &weights, bounds);
+}
+
+#[test]
+fn hidden_by_a_neighbor() {
+    // A heavy point swallows the cell of a light point next to it
+    let coords = vec![0.0, 0.0, 10.0, 0.0, 5.0, 10.0, 5.0, 3.0, 5.0, 4.0];
+    let weights = vec![0.0, 0.0, 0.0, 40.0, 0.0];
+    let d = Delaunator::with_weights(coords.clone(), weights.clone()).unwrap();
+    check(&d, &coords, &weights);
+    assert_eq!(d.hidden, [4]);
+    check_cells(&d, &coords, &weights, [-5.0, -5.0, 15.0, 15.0]);
+}
+
+#[test]
+fn huge_weights() {
+    // Lifting overflows, which is reported instead of panicking
+    let coords = vec![
+        0.0, 0.0, 10.0, 0.0, 10.0, 10.0, 0.0, 10.0, 5.0, 5.0, 2.0, 7.0,
+    ];
+    let weights = vec![
+        f64::MAX,
+        -f64::MAX,
+        f64::MAX,
+        -f64::MAX,
+        f64::MAX,
+        -f64::MAX,
+    ];
+    match Delaunator::with_weights(coords, weights) {
+        Ok(d) => assert!(d.validate().is_structurally_valid()),
+        Err(error) => assert_eq!(error, Error::CoordinateRange),
+    }
+}
+
+#[test]
+fn near_cocircular() {
+    // Points rounded off a circle, whose power tests round to the wrong
+    // sign in floating point. With equal weights the triangulation is the
+    // Delaunay one, checked with the in-circle test.
+    let coords = vec![
+        -0.33498047319742863,
+        1.2759331864254277,
+        -0.36993992899702444,
+        0.5762095318550392,
+        1.713763872259722,
+        -1.008455773235821,
+        2.0144101368819953,
+        2.6131450003113166,
+        2.0896482019321914,
+        -0.9184896969825638,
+        -0.37384858693725254,
+        0.6057374478733053,
+        -0.0608344584404914,
+        1.8883559475397087,
+    ];
+    let n = coords.len() / 2;
+    let d = Delaunator::with_weights(coords.clone(), vec![0.3; n]).unwrap();
+    assert!(d.hidden.is_empty());
+    assert_eq!(d.triangles.len(), 3 * (2 * n - d.hull.len() - 2));
+    let report = d.validate();
+    assert!(report.is_valid(), "{report}");
+    check_delaunay(&d, &coords);
+}
+
+// Checks that equally weighted points give a Delaunay triangulation, with
+// exact in-circle tests since lifting with fractional weights rounds
+fn check_delaunay(d: &Delaunator, coords: &[f64]) {
+    let n = coords.len() / 2;
+    let point = |i: u32| Coord {
+        x: coords[2 * i as usize],
+        y: coords[2 * i as usize + 1],
+    };
+    for t in d.triangles.chunks_exact(3) {
+        let [a, b, c] = [t[0], t[1], t[2]].map(point);
+        assert!(orient2d(a, b, c) < 0.0);
+        for p in 0..n as u32 {
+            assert!(incircle(a, b, c, point(p)) >= 0.0, "{p} is inside {t:?}");
+        }
+    }
+}
@@ -0,0 +1,33 @@
+//! Serde round trips, and invalid arrays rejected on deserialization
+#![cfg(feature = "serde")]
+
+mod common;
+
+use delaunator_rs::Delaunator;
+
+#[test]
+fn serde_round_trip() {
+    let coords = vec![0.0, 0.0, 1.0, 0.0, 1.0, 1.0, 0.0, 1.0, 0.5, 0.5];
+    let plain = common::triangulate(&coords);
+    let weighted =
+        Delaunator::with_weights(coords.clone(), vec![0.0, 0.0, 0.0, 0.0, -1.0]).unwrap();
+    assert_eq!(weighted.hidden, [4]);
+
+    for d in [plain, weighted] {
+        let json = serde_json::to_string(&d).unwrap();
+        let rebuilt: Delaunator = serde_json::from_str(&json).unwrap();
+        assert_eq!(rebuilt.coords(), d.coords());
+        assert_eq!(rebuilt.triangles, d.triangles);
+        assert_eq!(rebuilt.halfedges, d.halfedges);
+        assert_eq!(rebuilt.hull, d.hull);
+        assert_eq!(rebuilt.hidden, d.hidden);
+    }
+
+    // Two halfedges of the same triangle paired up
+    let json =
+        r#"{"coords":[0,0,1,0,0,1],"triangles":[0,2,1],"halfedges":[1,0,-1],"hull":[0,2,1]}"#;
+    let error = serde_json::from_str::<Delaunator>(json).unwrap_err();
+    assert!(error
+        .to_string()
+        .starts_with("Arrays do not form a valid triangulation"));
+}
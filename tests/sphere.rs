@@ -0,0 +1,185 @@
+//! Spherical Delaunay triangulations and their Voronoi cells
+
+use std::collections::BTreeSet;
+use std::f64::consts::PI;
+
+use delaunator_rs::sphere::SphericalDelaunay;
+use delaunator_rs::{next_halfedge, Error};
+use proptest::prelude::*;
+
+// Distinct points, so that the triangulation covers the sphere
+fn lon_lat() -> impl Strategy<Value = Vec<f64>> {
+    prop::collection::vec((-180.0..180.0, -90.0..90.0), 4..100)
+        .prop_filter("distinct points", |points| {
+            let distinct: BTreeSet<(u64, u64)> = points
+                .iter()
+                .map(|&(lon, lat): &(f64, f64)| (lon.to_bits(), lat.to_bits()))
+                .collect();
+            distinct.len() == points.len()
+        })
+        .prop_map(|points| {
+            points
+                .into_iter()
+                .flat_map(|(lon, lat)| [lon, lat])
+                .collect()
+        })
+}
+
+fn vector(lon: f64, lat: f64) -> [f64; 3] {
+    let (lon, lat) = (lon.to_radians(), lat.to_radians());
+    [lat.cos() * lon.cos(), lat.cos() * lon.sin(), lat.sin()]
+}
+
+fn vectors(points: &[f64]) -> Vec<[f64; 3]> {
+    points.chunks_exact(2).map(|p| vector(p[0], p[1])).collect()
+}
+
+fn dot(a: [f64; 3], b: [f64; 3]) -> f64 {
+    a[0] * b[0] + a[1] * b[1] + a[2] * b[2]
+}
+
+fn cross(a: [f64; 3], b: [f64; 3]) -> [f64; 3] {
+    [
+        a[1] * b[2] - a[2] * b[1],
+        a[2] * b[0] - a[0] * b[2],
+        a[0] * b[1] - a[1] * b[0],
+    ]
+}
+
+fn sub(a: [f64; 3], b: [f64; 3]) -> [f64; 3] {
+    [a[0] - b[0], a[1] - b[1], a[2] - b[2]]
+}
+
+// Total turn of a polygon around the point p, seen from outside: 2π when
+// it winds counterclockwise
+fn winding(p: [f64; 3], polygon: &[[f64; 3]]) -> f64 {
+    let axis = if p[0].abs() < 0.5 {
+        [1.0, 0.0, 0.0]
+    } else {
+        [0.0, 1.0, 0.0]
+    };
+    let u = cross(axis, p);
+    let w = cross(p, u);
+    let angle = |c: [f64; 3]| dot(c, w).atan2(dot(c, u));
+    (0..polygon.len())
+        .map(|k| {
+            let turn = angle(polygon[(k + 1) % polygon.len()]) - angle(polygon[k]);
+            (turn + 3.0 * PI).rem_euclid(2.0 * PI) - PI
+        })
+        .sum()
+}
+
+// Checks the halfedges, Euler's formula and the winding of the triangles
+fn check(sphere: &SphericalDelaunay, points: &[f64]) {
+    let (triangles, halfedges) = (&sphere.triangles, &sphere.halfedges);
+    assert_eq!(triangles.len(), halfedges.len());
+
+    // Closed surface: every halfedge has a twin going the other way
+    for e in 0..halfedges.len() {
+        let twin = halfedges[e];
+        assert!(twin >= 0, "halfedge {e} has no twin");
+        let twin = twin as usize;
+        assert_eq!(halfedges[twin], e as i32);
+        assert_eq!(triangles[e], triangles[next_halfedge(twin)]);
+        assert_eq!(triangles[next_halfedge(e)], triangles[twin]);
+    }
+
+    // V - E + F = 2 for a triangulation of the sphere
+    let vertices = triangles.iter().collect::<BTreeSet<_>>().len();
+    let edges = halfedges.len() / 2;
+    let faces = triangles.len() / 3;
+    assert_eq!(vertices + faces, edges + 2);
+
+    // Triangles wind clockwise seen from outside, which orients the plane
+    // of their circumcircle outwards, and no point is beyond that plane
+    let v = vectors(points);
+    for t in triangles.chunks_exact(3) {
+        let [a, b, c] = [t[0], t[1], t[2]].map(|i| v[i as usize]);
+        let normal = cross(sub(c, a), sub(b, a));
+        let offset = dot(normal, a);
+        for p in &v {
+            assert!(
+                dot(normal, *p) <= offset + 1e-9,
+                "triangle {t:?} is not Delaunay"
+            );
+        }
+    }
+}
+
+proptest! {
+    #[test]
+    fn random_points(points in lon_lat()) {
+        let sphere = SphericalDelaunay::new(points.clone()).unwrap();
+        check(&sphere, &points);
+    }
+
+    #[test]
+    fn voronoi_cells(points in lon_lat()) {
+        let sphere = SphericalDelaunay::new(points.clone()).unwrap();
+        let v = vectors(&points);
+        let cells = sphere.voronoi_cells();
+        prop_assert_eq!(cells.len(), v.len());
+
+        let used: BTreeSet<u32> = sphere.triangles.iter().copied().collect();
+        for (i, cell) in cells.iter().enumerate() {
+            if !used.contains(&(i as u32)) {
+                prop_assert!(cell.is_empty());
+                continue;
+            }
+
+            // One vertex per triangle around the point, counterclockwise
+            // seen from outside
+            let degree = sphere.triangles.iter().filter(|&&p| p == i as u32).count();
+            prop_assert_eq!(cell.len(), 2 * degree);
+            let turn = winding(v[i], &vectors(cell));
+            prop_assert!((turn - 2.0 * PI).abs() < 1e-6, "cell {} turns by {}", i, turn);
+        }
+    }
+}
+
+#[test]
+fn three_points() {
+    let points = vec![0.0, 0.0, 90.0, 0.0, 0.0, 90.0];
+    let sphere = SphericalDelaunay::new(points.clone()).unwrap();
+    assert_eq!(sphere.triangles.len(), 6);
+    check(&sphere, &points);
+
+    // Two antipodal circumcenters, the north-east octant's and its opposite
+    let centers = sphere.circumcenters();
+    let v = vectors(&centers);
+    assert!((dot(v[0], v[1]) + 1.0).abs() < 1e-12);
+
+    // Each cell is a lune between the two centers
+    assert!(sphere.voronoi_cells().iter().all(|cell| cell.len() == 4));
+}
+
+#[test]
+fn three_points_on_a_great_circle() {
+    let sphere = SphericalDelaunay::new(vec![0.0, 0.0, 90.0, 0.0, 180.0, 0.0]).unwrap();
+    assert!(sphere.triangles.is_empty());
+}
+
+#[test]
+fn octahedron() {
+    let points = vec![
+        0.0, 0.0, 90.0, 0.0, 180.0, 0.0, -90.0, 0.0, 0.0, 90.0, 0.0, -90.0,
+    ];
+    let sphere = SphericalDelaunay::new(points.clone()).unwrap();
+    // Cospherical, so every split of the equator's square is Delaunay
+    assert_eq!(sphere.triangles.len(), 3 * 8);
+    for e in 0..sphere.halfedges.len() {
+        assert_eq!(sphere.halfedges[sphere.halfedges[e] as usize], e as i32);
+    }
+}
+
+#[test]
+fn invalid_input() {
+    assert_eq!(
+        SphericalDelaunay::new(vec![0.0, 0.0, 1.0]).unwrap_err(),
+        Error::InvalidCoordinates
+    );
+    assert_eq!(
+        SphericalDelaunay::new(vec![0.0, f64::NAN]).unwrap_err(),
+        Error::NonFiniteCoordinate
+    );
+}
@@ -0,0 +1,40 @@
+//! Streamed triangulation of points read in order, one cell at a time
+
+mod common;
+
+use delaunator_rs::stream::{Cells, Stream};
+use delaunator_rs::Error;
+
+#[test]
+fn streamed_rows() {
+    // Jittered 100 x 100 lattice read row by row, one row of cells at a time
+    let side = 100;
+    let mut seed = 1u64;
+    let mut jitter = || {
+        seed = seed
+            .wrapping_mul(6364136223846793005)
+            .wrapping_add(1442695040888963407);
+        (seed >> 11) as f64 / (1u64 << 53) as f64 * 0.5
+    };
+    let coords: Vec<f64> = (0..side * side)
+        .flat_map(|i| [(i % side) as f64 + jitter(), (i / side) as f64 + jitter()])
+        .collect();
+
+    let cells = Cells::new([0.0, 0.0, side as f64, side as f64], 10, 10).unwrap();
+    let mut stream = Stream::new(cells, Vec::new());
+    let mut peak = 0;
+    for (i, p) in coords.chunks_exact(2).enumerate() {
+        assert_eq!(stream.insert(p[0], p[1]), Ok(i));
+        if (i + 1) % (10 * side) == 0 {
+            let row = i / (10 * side);
+            (10 * row..10 * row + 10).for_each(|c| stream.finalize(c));
+        }
+        peak = peak.max(stream.resident_points());
+    }
+    assert_eq!(stream.insert(1.0, 1.0), Err(Error::InvalidCoordinates));
+    assert!(peak < side * side / 4, "peak of {peak} points");
+
+    let streamed = stream.finish();
+    let d = common::triangulate(&coords);
+    assert_eq!(streamed.len(), d.triangles.len());
+}
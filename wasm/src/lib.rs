@@ -0,0 +1,6 @@
+//! WebAssembly package of delaunator-rs
+//!
+//! Re-exports the crate with the `wasm` feature enabled, so that wasm-pack
+//! links its bindings into the module. Build it with `./build.sh`.
+
+pub use delaunator_rs::*;